@@ -0,0 +1,447 @@
+//! Job Gap Analysis Module
+//!
+//! Compares the skills a job description asks for with the user's parsed
+//! resume and produces a structured gap report. Missing skills become
+//! suggested `CareerRule`s that the planner can turn into roadmap steps.
+//! Each user's latest report is kept in SQLite next to the memory timeline.
+
+use super::native_parser::{contains_word, extract_skills};
+use super::types::{CareerRule, ResumeData};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Tag attached to rules generated from a gap report
+pub const JOB_GAP_TAG: &str = "job_gap";
+
+/// Skills that count as partial evidence for each other (required, related)
+const RELATED_SKILLS: &[(&str, &str)] = &[
+    ("SQL", "PostgreSQL"),
+    ("SQL", "MySQL"),
+    ("PostgreSQL", "SQL"),
+    ("PostgreSQL", "MySQL"),
+    ("MySQL", "PostgreSQL"),
+    ("MySQL", "SQL"),
+    ("NoSQL", "MongoDB"),
+    ("MongoDB", "NoSQL"),
+    ("TypeScript", "JavaScript"),
+    ("JavaScript", "TypeScript"),
+    ("React", "JavaScript"),
+    ("Angular", "TypeScript"),
+    ("Vue", "JavaScript"),
+    ("Node.js", "JavaScript"),
+    ("Kubernetes", "Docker"),
+    ("Docker", "Kubernetes"),
+    ("AWS", "Azure"),
+    ("AWS", "GCP"),
+    ("Azure", "AWS"),
+    ("Azure", "GCP"),
+    ("GCP", "AWS"),
+    ("GCP", "Azure"),
+    ("PyTorch", "TensorFlow"),
+    ("TensorFlow", "PyTorch"),
+    ("Deep Learning", "Machine Learning"),
+    ("Machine Learning", "Python"),
+    ("Django", "Python"),
+    ("Flask", "Python"),
+    ("Spring", "Java"),
+    ("Kotlin", "Java"),
+    ("Rails", "Ruby"),
+    ("Laravel", "PHP"),
+    ("CI/CD", "Jenkins"),
+    ("Jenkins", "CI/CD"),
+    ("DevOps", "CI/CD"),
+    ("Scrum", "Agile"),
+    ("Agile", "Scrum"),
+    ("Power BI", "Tableau"),
+    ("Tableau", "Power BI"),
+];
+
+/// Phrases that mark a line or section as optional requirements
+const PREFERRED_MARKERS: &[&str] = &[
+    "nice to have",
+    "nice-to-have",
+    "preferred",
+    "bonus",
+    "a plus",
+    "is a plus",
+    "desirable",
+    "optional",
+];
+
+/// Phrases that start a section of hard requirements
+const REQUIRED_MARKERS: &[&str] = &[
+    "requirements",
+    "required",
+    "must have",
+    "must-have",
+    "qualifications",
+    "you have",
+    "what you bring",
+];
+
+// ============================================================
+// GAP REPORT STRUCTURES
+// ============================================================
+
+/// A skill the job asks for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JobSkill {
+    pub skill: String,
+    /// false for "nice to have" / "preferred" skills
+    pub required: bool,
+}
+
+/// A skill the user lacks but has related experience for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialMatch {
+    pub skill: String,
+    pub required: bool,
+    /// Skills from the resume that partially cover this one
+    pub related_skills: Vec<String>,
+}
+
+/// Result of comparing a job description with a resume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapReport {
+    pub id: String,
+    pub user_id: String,
+    pub generated_at: DateTime<Utc>,
+    pub job_title: Option<String>,
+    /// Every skill detected in the job description
+    pub job_skills: Vec<JobSkill>,
+    /// Skills the user already has
+    pub matched: Vec<String>,
+    /// Skills the user has adjacent experience for
+    pub partial_matches: Vec<PartialMatch>,
+    /// Skills with no evidence on the resume
+    pub missing: Vec<JobSkill>,
+    /// Weighted coverage of the job's skills (0.0 to 1.0)
+    pub match_score: f32,
+    /// Learning goals for the planner, one per missing or partial skill
+    pub suggested_goals: Vec<CareerRule>,
+}
+
+// ============================================================
+// ANALYSIS
+// ============================================================
+
+/// Detect skills in a job description, classifying each as required or preferred.
+///
+/// A skill is preferred if its line mentions a "nice to have" marker or it
+/// appears under a preferred section heading; otherwise it is required.
+pub fn extract_job_skills(job_description: &str) -> Vec<JobSkill> {
+    let mut skills: Vec<JobSkill> = Vec::new();
+    let mut in_preferred_section = false;
+
+    for line in job_description.lines() {
+        let lower = line.to_lowercase();
+        let line_skills = extract_skills(line);
+
+        // Short lines without skills are treated as section headings
+        if line_skills.is_empty() && line.split_whitespace().count() <= 6 {
+            if PREFERRED_MARKERS.iter().any(|m| lower.contains(m)) {
+                in_preferred_section = true;
+            } else if REQUIRED_MARKERS.iter().any(|m| lower.contains(m)) {
+                in_preferred_section = false;
+            }
+            continue;
+        }
+
+        let preferred = in_preferred_section || PREFERRED_MARKERS.iter().any(|m| lower.contains(m));
+        for skill in line_skills {
+            match skills.iter_mut().find(|s| s.skill == skill) {
+                // A skill listed as required anywhere stays required
+                Some(existing) => existing.required |= !preferred,
+                None => skills.push(JobSkill {
+                    skill,
+                    required: !preferred,
+                }),
+            }
+        }
+    }
+
+    skills
+}
+
+/// Compare a job description against a resume
+///
+/// # Arguments
+/// * `user_id` - Owner of the report
+/// * `job_title` - Optional title for display
+/// * `job_description` - Pasted job description text
+/// * `resume` - The user's parsed resume
+/// * `rules` - Known career rules, used to reuse estimates for suggested goals
+pub fn analyze_gap(
+    user_id: &str,
+    job_title: Option<&str>,
+    job_description: &str,
+    resume: &ResumeData,
+    rules: &[CareerRule],
+) -> GapReport {
+    let job_skills = extract_job_skills(job_description);
+
+    // Resume skills plus any known skill mentioned in the resume text
+    let mut have: HashSet<String> = resume.skills.iter().map(|s| s.to_lowercase()).collect();
+    have.extend(extract_skills(&resume.raw_text).iter().map(|s| s.to_lowercase()));
+
+    let mut matched = Vec::new();
+    let mut partial_matches = Vec::new();
+    let mut missing = Vec::new();
+
+    for job_skill in &job_skills {
+        if have.contains(&job_skill.skill.to_lowercase()) {
+            matched.push(job_skill.skill.clone());
+            continue;
+        }
+
+        let related: Vec<String> = RELATED_SKILLS
+            .iter()
+            .filter(|(skill, _)| skill.eq_ignore_ascii_case(&job_skill.skill))
+            .filter(|(_, related)| have.contains(&related.to_lowercase()))
+            .map(|(_, related)| related.to_string())
+            .collect();
+
+        if related.is_empty() {
+            missing.push(job_skill.clone());
+        } else {
+            partial_matches.push(PartialMatch {
+                skill: job_skill.skill.clone(),
+                required: job_skill.required,
+                related_skills: related,
+            });
+        }
+    }
+
+    let match_score = score(&job_skills, &matched, &partial_matches);
+
+    let mut suggested_goals: Vec<CareerRule> = missing
+        .iter()
+        .map(|m| suggested_rule(&m.skill, m.required, false, rules))
+        .collect();
+    suggested_goals.extend(
+        partial_matches
+            .iter()
+            .map(|p| suggested_rule(&p.skill, p.required, true, rules)),
+    );
+
+    GapReport {
+        id: Uuid::new_v4().to_string(),
+        user_id: user_id.to_string(),
+        generated_at: Utc::now(),
+        job_title: job_title.map(|t| t.to_string()),
+        job_skills,
+        matched,
+        partial_matches,
+        missing,
+        match_score,
+        suggested_goals,
+    }
+}
+
+/// Required skills weigh twice as much as preferred; partial matches count half.
+/// A job description with no recognized skills scores 0, not a full match.
+fn score(job_skills: &[JobSkill], matched: &[String], partial: &[PartialMatch]) -> f32 {
+    let weight = |required: bool| if required { 2.0 } else { 1.0 };
+    let total: f32 = job_skills.iter().map(|s| weight(s.required)).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    let covered: f32 = job_skills
+        .iter()
+        .map(|s| {
+            if matched.contains(&s.skill) {
+                weight(s.required)
+            } else if partial.iter().any(|p| p.skill == s.skill) {
+                weight(s.required) * 0.5
+            } else {
+                0.0
+            }
+        })
+        .sum();
+
+    covered / total
+}
+
+/// Build a learning goal for a skill gap, reusing an existing rule's estimate if one matches
+fn suggested_rule(skill: &str, required: bool, partial: bool, rules: &[CareerRule]) -> CareerRule {
+    let skill_lower = skill.to_lowercase();
+    let known = rules.iter().find(|r| {
+        contains_word(&r.title.to_lowercase(), &skill_lower)
+            || r.tags.iter().any(|t| t.eq_ignore_ascii_case(skill))
+    });
+
+    let id = format!(
+        "{}_{}",
+        JOB_GAP_TAG,
+        skill_lower
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+    );
+    let mut rule = CareerRule::new(&id, &format!("Learn {}", skill), "technical_skill");
+    rule.description = if partial {
+        format!(
+            "The target job asks for {}. Build on your related experience to close the gap.",
+            skill
+        )
+    } else {
+        format!("The target job asks for {}, which is not on your resume yet.", skill)
+    };
+    rule.priority = match (required, partial) {
+        (true, false) => "critical",
+        (true, true) => "high",
+        (false, _) => "medium",
+    }
+    .to_string();
+    rule.estimated_weeks = known
+        .and_then(|r| r.estimated_weeks)
+        .or(Some(if partial { 2 } else { 4 }));
+    rule.tags = vec![JOB_GAP_TAG.to_string(), skill.to_string()];
+    rule
+}
+
+// ============================================================
+// REPORT STORE
+// ============================================================
+
+/// Latest gap report per user, persisted alongside the memory timeline
+pub struct GapReportStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl GapReportStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> SqlResult<Self> {
+        conn.lock().unwrap().execute(
+            "CREATE TABLE IF NOT EXISTS gap_reports (
+                user_id TEXT PRIMARY KEY,
+                generated_at TEXT NOT NULL,
+                report TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Replace the user's latest report
+    pub fn save(&self, report: &GapReport) -> SqlResult<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO gap_reports (user_id, generated_at, report)
+             VALUES (?1, ?2, ?3)",
+            params![
+                report.user_id,
+                report.generated_at.to_rfc3339(),
+                serde_json::to_string(report).unwrap_or_default(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn latest(&self, user_id: &str) -> SqlResult<Option<GapReport>> {
+        let json: Option<String> = self
+            .conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT report FROM gap_reports WHERE user_id = ?1",
+                [user_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.and_then(|j| serde_json::from_str(&j).ok()))
+    }
+
+    /// Learning goals from the user's latest report (empty when there is none)
+    pub fn suggested_goals(&self, user_id: &str) -> Vec<CareerRule> {
+        self.latest(user_id)
+            .ok()
+            .flatten()
+            .map(|r| r.suggested_goals)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JOB: &str = "Backend Engineer\n\
+        \n\
+        Requirements\n\
+        - 3+ years with Python and PostgreSQL\n\
+        - Experience deploying with Docker and Kubernetes\n\
+        - Solid REST API design\n\
+        \n\
+        Nice to have\n\
+        - GraphQL\n\
+        - AWS certification\n";
+
+    #[test]
+    fn test_extract_job_skills_classifies_required_and_preferred() {
+        let skills = extract_job_skills(JOB);
+        let required = |name: &str| skills.iter().find(|s| s.skill == name).map(|s| s.required);
+
+        assert_eq!(required("Python"), Some(true));
+        assert_eq!(required("Kubernetes"), Some(true));
+        assert_eq!(required("GraphQL"), Some(false));
+        assert_eq!(required("AWS"), Some(false));
+    }
+
+    #[test]
+    fn test_analyze_gap() {
+        let resume = ResumeData {
+            user_id: "test_user".to_string(),
+            skills: vec!["Python".to_string(), "MySQL".to_string(), "Docker".to_string()],
+            ..Default::default()
+        };
+
+        let report = analyze_gap("test_user", Some("Backend Engineer"), JOB, &resume, &[]);
+
+        assert!(report.matched.contains(&"Python".to_string()));
+        assert!(report.matched.contains(&"Docker".to_string()));
+        assert!(report
+            .partial_matches
+            .iter()
+            .any(|p| p.skill == "PostgreSQL" && p.related_skills == vec!["MySQL".to_string()]));
+        assert!(report.partial_matches.iter().any(|p| p.skill == "Kubernetes"));
+        assert!(report.missing.iter().any(|m| m.skill == "REST" && m.required));
+        assert!(report.missing.iter().any(|m| m.skill == "GraphQL" && !m.required));
+        assert!(report.match_score > 0.0 && report.match_score < 1.0);
+
+        let goal = |title: &str| report.suggested_goals.iter().find(|r| r.title == title).unwrap();
+        assert_eq!(goal("Learn REST").priority, "critical");
+        assert_eq!(goal("Learn Kubernetes").priority, "high");
+        assert_eq!(goal("Learn GraphQL").priority, "medium");
+        assert!(goal("Learn REST").tags.contains(&JOB_GAP_TAG.to_string()));
+        assert_eq!(
+            report.suggested_goals.len(),
+            report.missing.len() + report.partial_matches.len()
+        );
+
+        let store = GapReportStore::new(Arc::new(Mutex::new(Connection::open_in_memory().unwrap()))).unwrap();
+        assert!(store.suggested_goals("test_user").is_empty());
+        store.save(&report).unwrap();
+        assert_eq!(store.latest("test_user").unwrap().unwrap().id, report.id);
+        assert_eq!(store.suggested_goals("test_user").len(), report.suggested_goals.len());
+    }
+
+    #[test]
+    fn test_unrecognized_job_and_short_skill_names() {
+        let resume = ResumeData::default();
+        let report = analyze_gap("u1", None, "Must be a great team player.", &resume, &[]);
+        assert!(report.job_skills.is_empty());
+        assert_eq!(report.match_score, 0.0);
+
+        let mut algorithms = CareerRule::new("algorithms", "Master Algorithms", "technical_skill");
+        algorithms.estimated_weeks = Some(12);
+        let mut go = CareerRule::new("go_basics", "Learn Go", "technical_skill");
+        go.estimated_weeks = Some(3);
+
+        assert_eq!(suggested_rule("Go", true, false, &[algorithms.clone()]).estimated_weeks, Some(4));
+        assert_eq!(suggested_rule("Go", true, false, &[algorithms, go]).estimated_weeks, Some(3));
+    }
+}
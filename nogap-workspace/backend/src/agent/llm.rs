@@ -0,0 +1,411 @@
+//! LLM Provider Module
+//!
+//! Pluggable text-generation backends. Today only the weekly reflection
+//! summary (LEARN phase) is written through a provider; sensing and planning
+//! stay rule-based. Providers speak either the OpenAI-compatible chat
+//! completions API or a local Ollama server, so reflections can be written
+//! offline. When no provider is configured the rule-based summary is used.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default base URL for a local Ollama server
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Default base URL for the OpenAI API
+pub const DEFAULT_OPENAI_URL: &str = "https://api.openai.com/v1";
+
+// ============================================================
+// PROVIDER TRAIT
+// ============================================================
+
+/// One message in a chat-style prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatMessage {
+    /// "system", "user" or "assistant"
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: &str) -> Self {
+        Self {
+            role: "system".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    pub fn user(content: &str) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum LlmError {
+    #[error("LLM request failed: {0}")]
+    Request(String),
+
+    #[error("Unexpected LLM response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Invalid LLM configuration: {0}")]
+    Config(String),
+}
+
+/// A backend that turns a chat prompt into a completion
+pub trait LlmProvider: Send + Sync {
+    /// Short provider name for logs ("openai", "ollama", "mock")
+    fn name(&self) -> &str;
+
+    /// Model identifier sent to the backend
+    fn model(&self) -> &str;
+
+    /// Generate a completion for the given messages
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError>;
+}
+
+// ============================================================
+// CONFIGURATION
+// ============================================================
+
+/// Which provider to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmProviderKind {
+    /// Rule-based text only
+    None,
+    OpenAi,
+    Ollama,
+    Mock,
+}
+
+impl LlmProviderKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "none" | "off" => Some(LlmProviderKind::None),
+            "openai" => Some(LlmProviderKind::OpenAi),
+            "ollama" => Some(LlmProviderKind::Ollama),
+            "mock" => Some(LlmProviderKind::Mock),
+            _ => None,
+        }
+    }
+}
+
+/// LLM settings
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    pub provider: LlmProviderKind,
+    /// API base URL; defaults depend on the provider
+    pub base_url: Option<String>,
+    /// Bearer key for OpenAI-compatible APIs (optional for local servers)
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub timeout_secs: u64,
+    pub temperature: f32,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            provider: LlmProviderKind::None,
+            base_url: None,
+            api_key: None,
+            model: None,
+            timeout_secs: 60,
+            temperature: 0.3,
+        }
+    }
+}
+
+impl LlmConfig {
+    /// Read `CAREER_AGENT_LLM_PROVIDER` (none/openai/ollama/mock),
+    /// `CAREER_AGENT_LLM_BASE_URL`, `CAREER_AGENT_LLM_MODEL`,
+    /// `CAREER_AGENT_LLM_API_KEY` (falls back to `OPENAI_API_KEY`)
+    /// and `CAREER_AGENT_LLM_TIMEOUT_SECS` (at least 1)
+    pub fn from_env() -> Result<Self, LlmError> {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let provider = match var("CAREER_AGENT_LLM_PROVIDER") {
+            Some(p) => LlmProviderKind::parse(&p)
+                .ok_or_else(|| LlmError::Config(format!("unknown provider '{}'", p)))?,
+            None => defaults.provider,
+        };
+
+        Ok(Self {
+            provider,
+            base_url: var("CAREER_AGENT_LLM_BASE_URL"),
+            api_key: var("CAREER_AGENT_LLM_API_KEY").or_else(|| var("OPENAI_API_KEY")),
+            model: var("CAREER_AGENT_LLM_MODEL"),
+            timeout_secs: var("CAREER_AGENT_LLM_TIMEOUT_SECS")
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|secs| secs.max(1))
+                .unwrap_or(defaults.timeout_secs),
+            temperature: defaults.temperature,
+        })
+    }
+}
+
+/// Build the configured provider, or `None` for rule-based mode
+pub fn build_provider(config: &LlmConfig) -> Result<Option<Arc<dyn LlmProvider>>, LlmError> {
+    let provider: Arc<dyn LlmProvider> = match config.provider {
+        LlmProviderKind::None => return Ok(None),
+        LlmProviderKind::OpenAi => {
+            if config.api_key.is_none() && config.base_url.is_none() {
+                return Err(LlmError::Config(
+                    "the OpenAI API needs CAREER_AGENT_LLM_API_KEY".to_string(),
+                ));
+            }
+            Arc::new(OpenAiProvider::new(config))
+        }
+        LlmProviderKind::Ollama => Arc::new(OllamaProvider::new(config)),
+        LlmProviderKind::Mock => Arc::new(MockProvider::new("Mock LLM response.")),
+    };
+    Ok(Some(provider))
+}
+
+// ============================================================
+// OPENAI-COMPATIBLE PROVIDER
+// ============================================================
+
+/// Chat completions client for OpenAI and compatible servers
+/// (vLLM, LM Studio, llama.cpp server, ...)
+pub struct OpenAiProvider {
+    agent: ureq::Agent,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: f32,
+}
+
+impl OpenAiProvider {
+    pub fn new(config: &LlmConfig) -> Self {
+        Self {
+            agent: http_agent(config.timeout_secs),
+            base_url: config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_OPENAI_URL.to_string()),
+            api_key: config.api_key.clone(),
+            model: config.model.clone().unwrap_or_else(|| "gpt-4o-mini".to_string()),
+            temperature: config.temperature,
+        }
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError> {
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+        let mut request = self.agent.post(&url);
+        if let Some(key) = &self.api_key {
+            request = request.set("Authorization", &format!("Bearer {}", key));
+        }
+
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "temperature": self.temperature,
+        });
+        let response: Value = request
+            .send_json(body)
+            .map_err(|e| LlmError::Request(e.to_string()))?
+            .into_json()
+            .map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+        parse_openai_response(&response)
+    }
+}
+
+/// Extract the first choice's message content
+fn parse_openai_response(response: &Value) -> Result<String, LlmError> {
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| LlmError::InvalidResponse(response.to_string()))
+}
+
+// ============================================================
+// OLLAMA PROVIDER
+// ============================================================
+
+/// Client for a local Ollama server (`/api/chat`)
+pub struct OllamaProvider {
+    agent: ureq::Agent,
+    base_url: String,
+    model: String,
+    temperature: f32,
+}
+
+impl OllamaProvider {
+    pub fn new(config: &LlmConfig) -> Self {
+        Self {
+            agent: http_agent(config.timeout_secs),
+            base_url: config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_OLLAMA_URL.to_string()),
+            model: config.model.clone().unwrap_or_else(|| "llama3.1".to_string()),
+            temperature: config.temperature,
+        }
+    }
+}
+
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError> {
+        let url = format!("{}/api/chat", self.base_url.trim_end_matches('/'));
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "stream": false,
+            "options": { "temperature": self.temperature },
+        });
+        let response: Value = self
+            .agent
+            .post(&url)
+            .send_json(body)
+            .map_err(|e| LlmError::Request(e.to_string()))?
+            .into_json()
+            .map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+        parse_ollama_response(&response)
+    }
+}
+
+/// Extract the reply from a non-streaming `/api/chat` response
+fn parse_ollama_response(response: &Value) -> Result<String, LlmError> {
+    if let Some(error) = response["error"].as_str() {
+        return Err(LlmError::Request(error.to_string()));
+    }
+    response["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| LlmError::InvalidResponse(response.to_string()))
+}
+
+pub(crate) fn http_agent(timeout_secs: u64) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(timeout_secs.max(1)))
+        .build()
+}
+
+// ============================================================
+// MOCK PROVIDER
+// ============================================================
+
+/// Deterministic provider for tests and demos.
+/// Returns queued responses in order, then the default response.
+pub struct MockProvider {
+    responses: Mutex<VecDeque<String>>,
+    default_response: String,
+    prompts: Mutex<Vec<Vec<ChatMessage>>>,
+}
+
+impl MockProvider {
+    pub fn new(default_response: &str) -> Self {
+        Self {
+            responses: Mutex::new(VecDeque::new()),
+            default_response: default_response.to_string(),
+            prompts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue a response for the next call
+    pub fn push_response(&self, response: &str) {
+        self.responses.lock().unwrap().push_back(response.to_string());
+    }
+
+    /// Every prompt received so far
+    pub fn prompts(&self) -> Vec<Vec<ChatMessage>> {
+        self.prompts.lock().unwrap().clone()
+    }
+}
+
+impl LlmProvider for MockProvider {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn model(&self) -> &str {
+        "mock"
+    }
+
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String, LlmError> {
+        self.prompts.lock().unwrap().push(messages.to_vec());
+        Ok(self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| self.default_response.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider_responses() {
+        let openai = json!({
+            "choices": [{ "message": { "role": "assistant", "content": " Keep going. " } }]
+        });
+        assert_eq!(parse_openai_response(&openai).unwrap(), "Keep going.");
+
+        let ollama = json!({ "message": { "role": "assistant", "content": "Nice week." }, "done": true });
+        assert_eq!(parse_ollama_response(&ollama).unwrap(), "Nice week.");
+
+        let missing_model = json!({ "error": "model 'llama3.1' not found" });
+        assert!(matches!(
+            parse_ollama_response(&missing_model),
+            Err(LlmError::Request(_))
+        ));
+        assert!(parse_openai_response(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_mock_provider_and_config() {
+        let mock = MockProvider::new("default");
+        mock.push_response("first");
+
+        let prompt = vec![ChatMessage::system("Be brief."), ChatMessage::user("Hi")];
+        assert_eq!(mock.complete(&prompt).unwrap(), "first");
+        assert_eq!(mock.complete(&prompt).unwrap(), "default");
+        assert_eq!(mock.prompts().len(), 2);
+
+        assert!(build_provider(&LlmConfig::default()).unwrap().is_none());
+        let ollama = build_provider(&LlmConfig {
+            provider: LlmProviderKind::Ollama,
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(ollama.name(), "ollama");
+        assert!(build_provider(&LlmConfig {
+            provider: LlmProviderKind::OpenAi,
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -7,8 +7,10 @@
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqlResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use thiserror::Error;
 use uuid::Uuid;
 
 // ============================================================
@@ -29,6 +31,7 @@ pub enum MemoryEventType {
     ReflectionGenerated,
     CheckpointCreated,
     AssessmentUpdated,
+    JobGapAnalyzed,
 }
 
 impl MemoryEventType {
@@ -44,6 +47,7 @@ impl MemoryEventType {
             MemoryEventType::ReflectionGenerated => "reflection_generated",
             MemoryEventType::CheckpointCreated => "checkpoint_created",
             MemoryEventType::AssessmentUpdated => "assessment_updated",
+            MemoryEventType::JobGapAnalyzed => "job_gap_analyzed",
         }
     }
 
@@ -59,9 +63,46 @@ impl MemoryEventType {
             "reflection_generated" => Some(MemoryEventType::ReflectionGenerated),
             "checkpoint_created" => Some(MemoryEventType::CheckpointCreated),
             "assessment_updated" => Some(MemoryEventType::AssessmentUpdated),
+            "job_gap_analyzed" => Some(MemoryEventType::JobGapAnalyzed),
             _ => None,
         }
     }
+
+    /// Which phase of the Sense → Plan → Learn loop produced this event
+    pub fn phase(&self) -> &'static str {
+        match self {
+            MemoryEventType::ResumeUploaded
+            | MemoryEventType::GoalSet
+            | MemoryEventType::GoalUpdated
+            | MemoryEventType::AssessmentUpdated
+            | MemoryEventType::JobGapAnalyzed => "sense",
+            MemoryEventType::PlanGenerated
+            | MemoryEventType::PlanModified
+            | MemoryEventType::StepCompleted
+            | MemoryEventType::StepSkipped => "plan",
+            MemoryEventType::ReflectionGenerated | MemoryEventType::CheckpointCreated => "learn",
+        }
+    }
+
+    /// All event types belonging to a loop phase ("sense", "plan", "learn")
+    pub fn for_phase(phase: &str) -> Vec<Self> {
+        [
+            MemoryEventType::ResumeUploaded,
+            MemoryEventType::PlanGenerated,
+            MemoryEventType::PlanModified,
+            MemoryEventType::StepCompleted,
+            MemoryEventType::StepSkipped,
+            MemoryEventType::GoalSet,
+            MemoryEventType::GoalUpdated,
+            MemoryEventType::ReflectionGenerated,
+            MemoryEventType::CheckpointCreated,
+            MemoryEventType::AssessmentUpdated,
+            MemoryEventType::JobGapAnalyzed,
+        ]
+        .into_iter()
+        .filter(|t| t.phase() == phase)
+        .collect()
+    }
 }
 
 // ============================================================
@@ -78,6 +119,9 @@ pub struct MemoryEvent {
     pub description: String,
     /// Optional structured metadata (JSON)
     pub metadata: Option<serde_json::Value>,
+    /// Free-form tags for filtering (e.g. skill IDs, "weekly")
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl MemoryEvent {
@@ -89,6 +133,7 @@ impl MemoryEvent {
             event_type,
             description: description.to_string(),
             metadata: None,
+            tags: Vec::new(),
         }
     }
 
@@ -96,6 +141,11 @@ impl MemoryEvent {
         self.metadata = Some(metadata);
         self
     }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
 // ============================================================
@@ -139,10 +189,99 @@ impl CareerMemory {
     }
 }
 
+// ============================================================
+// MEMORY QUERY
+// ============================================================
+
+/// Filter and pagination options for reading the timeline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryQuery {
+    /// Only events of this type
+    pub event_type: Option<MemoryEventType>,
+    /// Only events from this loop phase: "sense", "plan", "learn"
+    pub phase: Option<String>,
+    /// Only events carrying this tag
+    pub tag: Option<String>,
+    /// Only events at or after this time (RFC 3339)
+    #[serde(default, deserialize_with = "deserialize_query_time")]
+    pub since: Option<DateTime<Utc>>,
+    /// Only events before this time (RFC 3339)
+    #[serde(default, deserialize_with = "deserialize_query_time")]
+    pub until: Option<DateTime<Utc>>,
+    /// Page size (defaults to 50, capped at 500)
+    pub limit: Option<u32>,
+    /// Number of matching events to skip
+    pub offset: Option<u32>,
+}
+
+impl MemoryQuery {
+    pub const DEFAULT_LIMIT: u32 = 50;
+    pub const MAX_LIMIT: u32 = 500;
+
+    fn effective_limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT)
+    }
+}
+
+/// Why a timeline query failed
+#[derive(Error, Debug)]
+pub enum MemoryQueryError {
+    /// The query itself is invalid (client error)
+    #[error("Unknown phase: {0} (expected sense, plan or learn)")]
+    InvalidPhase(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+}
+
+/// Parse an RFC 3339 timestamp from a query string. An unencoded `+` in the
+/// offset arrives as a space, so `...T10:00:00 00:00` is read as `+00:00`.
+pub fn parse_query_time(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|e| match value.rsplit_once(' ') {
+            Some((time, offset)) => DateTime::parse_from_rfc3339(&format!("{}+{}", time, offset)),
+            None => Err(e),
+        })
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn deserialize_query_time<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    match value {
+        Some(v) => parse_query_time(&v)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid RFC 3339 timestamp: {}", v))),
+        None => Ok(None),
+    }
+}
+
+/// One page of timeline events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryPage {
+    pub user_id: String,
+    pub events: Vec<MemoryEvent>,
+    /// Total events matching the filter (across all pages)
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
 // ============================================================
 // MEMORY STORE (SQLite-backed)
 // ============================================================
 
+/// Default database file, overridable with `CAREER_AGENT_DB`
+pub const DEFAULT_DB_PATH: &str = "career_memory.db";
+
+const EVENT_COLUMNS: &str = "id, user_id, timestamp, event_type, description, metadata";
+
 /// SQLite-backed persistent memory store
 pub struct MemoryStore {
     conn: Arc<Mutex<Connection>>,
@@ -151,10 +290,40 @@ pub struct MemoryStore {
 impl MemoryStore {
     /// Create a new memory store with SQLite backend
     pub fn new(db_path: Option<PathBuf>) -> SqlResult<Self> {
-        let path = db_path.unwrap_or_else(|| PathBuf::from("career_memory.db"));
+        let path = db_path.unwrap_or_else(|| PathBuf::from(DEFAULT_DB_PATH));
         let conn = Connection::open(path)?;
-        
-        // Initialize schema
+        Self::init_schema(&conn)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Create a store using `CAREER_AGENT_DB` (or the default file)
+    pub fn from_env() -> SqlResult<Self> {
+        Self::new(std::env::var("CAREER_AGENT_DB").ok().map(PathBuf::from))
+    }
+
+    /// Create an in-memory store for testing
+    pub fn in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        Self::init_schema(&conn)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Shared connection, for other stores that live in the same database
+    pub fn connection(&self) -> Arc<Mutex<Connection>> {
+        Arc::clone(&self.conn)
+    }
+
+    fn init_schema(conn: &Connection) -> SqlResult<()> {
+        // SQLite leaves foreign keys off per connection; the tag and
+        // embedding tables rely on ON DELETE CASCADE
+        conn.execute_batch("PRAGMA foreign_keys = ON")?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS memory_events (
                 id TEXT PRIMARY KEY,
@@ -167,6 +336,15 @@ impl MemoryStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS memory_event_tags (
+                event_id TEXT NOT NULL REFERENCES memory_events(id) ON DELETE CASCADE,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (event_id, tag)
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_memory_user_id ON memory_events(user_id)",
             [],
@@ -177,38 +355,21 @@ impl MemoryStore {
             [],
         )?;
 
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
-    }
-
-    /// Create an in-memory store for testing
-    pub fn in_memory() -> SqlResult<Self> {
-        let conn = Connection::open_in_memory()?;
-        
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS memory_events (
-                id TEXT PRIMARY KEY,
-                user_id TEXT NOT NULL,
-                timestamp TEXT NOT NULL,
-                event_type TEXT NOT NULL,
-                description TEXT NOT NULL,
-                metadata TEXT
-            )",
+            "CREATE INDEX IF NOT EXISTS idx_memory_tag ON memory_event_tags(tag)",
             [],
         )?;
 
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+        Ok(())
     }
 
     /// Record a new event
     pub fn record_event(&self, event: &MemoryEvent) -> SqlResult<()> {
-        let conn = self.conn.lock().unwrap();
+        let mut conn = self.conn.lock().unwrap();
         let metadata_json = event.metadata.as_ref().map(|m| m.to_string());
-        
-        conn.execute(
+
+        let tx = conn.transaction()?;
+        tx.execute(
             "INSERT INTO memory_events (id, user_id, timestamp, event_type, description, metadata)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
@@ -220,40 +381,29 @@ impl MemoryStore {
                 metadata_json,
             ],
         )?;
-        
-        Ok(())
+        for tag in &event.tags {
+            tx.execute(
+                "INSERT OR IGNORE INTO memory_event_tags (event_id, tag) VALUES (?1, ?2)",
+                params![event.id, tag],
+            )?;
+        }
+        tx.commit()
     }
 
     /// Get all events for a user
     pub fn get_user_memory(&self, user_id: &str) -> SqlResult<CareerMemory> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT id, user_id, timestamp, event_type, description, metadata
-             FROM memory_events
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_events
              WHERE user_id = ?1
-             ORDER BY timestamp ASC"
-        )?;
+             ORDER BY timestamp ASC",
+            EVENT_COLUMNS
+        ))?;
+
+        let events = stmt.query_map([user_id], row_to_event)?;
+        let mut timeline: Vec<MemoryEvent> = events.filter_map(|e| e.ok()).collect();
+        load_tags(&conn, &mut timeline)?;
 
-        let events = stmt.query_map([user_id], |row| {
-            let event_type_str: String = row.get(3)?;
-            let metadata_str: Option<String> = row.get(5)?;
-            let timestamp_str: String = row.get(2)?;
-            
-            Ok(MemoryEvent {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                timestamp: DateTime::parse_from_rfc3339(&timestamp_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                event_type: MemoryEventType::from_str(&event_type_str)
-                    .unwrap_or(MemoryEventType::CheckpointCreated),
-                description: row.get(4)?,
-                metadata: metadata_str.and_then(|s| serde_json::from_str(&s).ok()),
-            })
-        })?;
-
-        let timeline: Vec<MemoryEvent> = events.filter_map(|e| e.ok()).collect();
-        
         Ok(CareerMemory {
             user_id: user_id.to_string(),
             timeline,
@@ -264,36 +414,142 @@ impl MemoryStore {
     pub fn get_recent_events(&self, user_id: &str, days: i64) -> SqlResult<Vec<MemoryEvent>> {
         let cutoff = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
         let conn = self.conn.lock().unwrap();
-        
-        let mut stmt = conn.prepare(
-            "SELECT id, user_id, timestamp, event_type, description, metadata
-             FROM memory_events
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_events
              WHERE user_id = ?1 AND timestamp >= ?2
-             ORDER BY timestamp ASC"
+             ORDER BY timestamp ASC",
+            EVENT_COLUMNS
+        ))?;
+
+        let events = stmt.query_map(params![user_id, cutoff], row_to_event)?;
+        let mut timeline: Vec<MemoryEvent> = events.filter_map(|e| e.ok()).collect();
+        load_tags(&conn, &mut timeline)?;
+
+        Ok(timeline)
+    }
+
+    /// Get a filtered, paginated slice of a user's timeline (newest first)
+    pub fn query_events(
+        &self,
+        user_id: &str,
+        query: &MemoryQuery,
+    ) -> Result<MemoryPage, MemoryQueryError> {
+        let mut clauses = vec!["user_id = ?".to_string()];
+        let mut args: Vec<String> = vec![user_id.to_string()];
+
+        if let Some(event_type) = &query.event_type {
+            clauses.push("event_type = ?".to_string());
+            args.push(event_type.as_str().to_string());
+        }
+        if let Some(phase) = &query.phase {
+            let types = MemoryEventType::for_phase(phase);
+            if types.is_empty() {
+                return Err(MemoryQueryError::InvalidPhase(phase.clone()));
+            }
+            clauses.push(format!(
+                "event_type IN ({})",
+                vec!["?"; types.len()].join(", ")
+            ));
+            args.extend(types.iter().map(|t| t.as_str().to_string()));
+        }
+        if let Some(tag) = &query.tag {
+            clauses.push(
+                "EXISTS (SELECT 1 FROM memory_event_tags t
+                         WHERE t.event_id = memory_events.id AND t.tag = ?)"
+                    .to_string(),
+            );
+            args.push(tag.clone());
+        }
+        if let Some(since) = &query.since {
+            clauses.push("timestamp >= ?".to_string());
+            args.push(since.to_rfc3339());
+        }
+        if let Some(until) = &query.until {
+            clauses.push("timestamp < ?".to_string());
+            args.push(until.to_rfc3339());
+        }
+
+        let where_clause = clauses.join(" AND ");
+        let limit = query.effective_limit();
+        let offset = query.offset.unwrap_or(0);
+
+        let conn = self.conn.lock().unwrap();
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM memory_events WHERE {}", where_clause),
+            rusqlite::params_from_iter(args.iter()),
+            |row| row.get(0),
         )?;
 
-        let events = stmt.query_map(params![user_id, cutoff], |row| {
-            let event_type_str: String = row.get(3)?;
-            let metadata_str: Option<String> = row.get(5)?;
-            let timestamp_str: String = row.get(2)?;
-            
-            Ok(MemoryEvent {
-                id: row.get(0)?,
-                user_id: row.get(1)?,
-                timestamp: DateTime::parse_from_rfc3339(&timestamp_str)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                event_type: MemoryEventType::from_str(&event_type_str)
-                    .unwrap_or(MemoryEventType::CheckpointCreated),
-                description: row.get(4)?,
-                metadata: metadata_str.and_then(|s| serde_json::from_str(&s).ok()),
-            })
-        })?;
-
-        Ok(events.filter_map(|e| e.ok()).collect())
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_events
+             WHERE {}
+             ORDER BY timestamp DESC, id DESC
+             LIMIT {} OFFSET {}",
+            EVENT_COLUMNS, where_clause, limit, offset
+        ))?;
+        let events = stmt.query_map(rusqlite::params_from_iter(args.iter()), row_to_event)?;
+        let mut page: Vec<MemoryEvent> = events.filter_map(|e| e.ok()).collect();
+        load_tags(&conn, &mut page)?;
+
+        Ok(MemoryPage {
+            user_id: user_id.to_string(),
+            events: page,
+            total: total as u64,
+            limit,
+            offset,
+        })
     }
 }
 
+/// Map a `memory_events` row (selected with `EVENT_COLUMNS`) to an event
+fn row_to_event(row: &rusqlite::Row<'_>) -> SqlResult<MemoryEvent> {
+    let event_type_str: String = row.get(3)?;
+    let metadata_str: Option<String> = row.get(5)?;
+    let timestamp_str: String = row.get(2)?;
+
+    Ok(MemoryEvent {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        timestamp: DateTime::parse_from_rfc3339(&timestamp_str)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        event_type: MemoryEventType::from_str(&event_type_str)
+            .unwrap_or(MemoryEventType::CheckpointCreated),
+        description: row.get(4)?,
+        metadata: metadata_str.and_then(|s| serde_json::from_str(&s).ok()),
+        tags: Vec::new(),
+    })
+}
+
+/// Events per tag lookup, kept under SQLite's bound-parameter limit
+const TAG_BATCH: usize = 500;
+
+/// Fill in the tags for a batch of events
+fn load_tags(conn: &Connection, events: &mut [MemoryEvent]) -> SqlResult<()> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    for chunk in events.chunks(TAG_BATCH) {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT event_id, tag FROM memory_event_tags
+             WHERE event_id IN ({})
+             ORDER BY tag",
+            vec!["?"; chunk.len()].join(", ")
+        ))?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(chunk.iter().map(|e| &e.id)),
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?;
+        for (event_id, tag) in rows.filter_map(|r| r.ok()) {
+            tags.entry(event_id).or_default().push(tag);
+        }
+    }
+    for event in events.iter_mut() {
+        event.tags = tags.remove(&event.id).unwrap_or_default();
+    }
+    Ok(())
+}
+
 // ============================================================
 // HELPER FUNCTIONS
 // ============================================================
@@ -338,6 +594,28 @@ pub fn record_step_completed(store: &MemoryStore, user_id: &str, step_title: &st
     store.record_event(&event)
 }
 
+/// Record a job description gap analysis event
+pub fn record_gap_analysis(
+    store: &MemoryStore,
+    user_id: &str,
+    job_title: Option<&str>,
+    missing_count: usize,
+    match_score: f32,
+) -> SqlResult<()> {
+    let event = MemoryEvent::new(
+        user_id,
+        MemoryEventType::JobGapAnalyzed,
+        &format!(
+            "Analyzed job description{}: {:.0}% match, {} skill gap(s)",
+            job_title.map(|t| format!(" for {}", t)).unwrap_or_default(),
+            match_score * 100.0,
+            missing_count
+        ),
+    )
+    .with_tags(vec![super::gap_analysis::JOB_GAP_TAG.to_string()]);
+    store.record_event(&event)
+}
+
 /// Record a reflection generation event
 pub fn record_reflection(store: &MemoryStore, user_id: &str, reflection_summary: &str) -> SqlResult<()> {
     let event = MemoryEvent::new(
@@ -369,4 +647,117 @@ mod tests {
         assert_eq!(memory.timeline[1].event_type, MemoryEventType::PlanGenerated);
         assert_eq!(memory.timeline[2].event_type, MemoryEventType::StepCompleted);
     }
+
+    #[test]
+    fn test_query_events_filters_and_paginates() {
+        let store = MemoryStore::in_memory().unwrap();
+        let user_id = "test_user";
+
+        record_resume_upload(&store, user_id, "resume.pdf").unwrap();
+        for i in 0..5 {
+            let event = MemoryEvent::new(
+                user_id,
+                MemoryEventType::StepCompleted,
+                &format!("Completed: Step {}", i),
+            )
+            .with_tags(vec!["rust".to_string()]);
+            store.record_event(&event).unwrap();
+        }
+        record_resume_upload(&store, "other_user", "resume.pdf").unwrap();
+
+        let page = store
+            .query_events(user_id, &MemoryQuery { limit: Some(2), ..Default::default() })
+            .unwrap();
+        assert_eq!(page.total, 6);
+        assert_eq!(page.events.len(), 2);
+
+        let page = store
+            .query_events(
+                user_id,
+                &MemoryQuery {
+                    tag: Some("rust".to_string()),
+                    offset: Some(4),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].tags, vec!["rust".to_string()]);
+
+        let page = store
+            .query_events(
+                user_id,
+                &MemoryQuery { phase: Some("sense".to_string()), ..Default::default() },
+            )
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.events[0].event_type, MemoryEventType::ResumeUploaded);
+
+        let result = store.query_events(
+            user_id,
+            &MemoryQuery { phase: Some("sens".to_string()), ..Default::default() },
+        );
+        assert!(matches!(result, Err(MemoryQueryError::InvalidPhase(_))));
+
+        // Events sharing a timestamp page in a stable order
+        let timestamp = Utc::now();
+        for i in 0..4 {
+            let mut event =
+                MemoryEvent::new("tied_user", MemoryEventType::StepCompleted, &format!("Step {}", i));
+            event.timestamp = timestamp;
+            store.record_event(&event).unwrap();
+        }
+        let mut seen: Vec<String> = Vec::new();
+        for offset in [0, 2] {
+            let page = store
+                .query_events(
+                    "tied_user",
+                    &MemoryQuery { limit: Some(2), offset: Some(offset), ..Default::default() },
+                )
+                .unwrap();
+            seen.extend(page.events.into_iter().map(|e| e.id));
+        }
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_query_time_and_tag_cascade() {
+        let expected = parse_query_time("2026-10-15T10:00:00Z").unwrap();
+        assert_eq!(parse_query_time("2026-10-15T10:00:00+00:00"), Some(expected));
+        // "+" decoded as a space from an unencoded query string
+        assert_eq!(parse_query_time("2026-10-15T10:00:00 00:00"), Some(expected));
+        assert_eq!(parse_query_time("yesterday"), None);
+
+        let store = MemoryStore::in_memory().unwrap();
+        let event = MemoryEvent::new("u1", MemoryEventType::StepCompleted, "Completed: SQL")
+            .with_tags(vec!["sql".to_string()]);
+        store.record_event(&event).unwrap();
+
+        let conn = store.connection();
+        let conn = conn.lock().unwrap();
+        conn.execute("DELETE FROM memory_events WHERE id = ?1", [&event.id]).unwrap();
+        let tags: i64 = conn
+            .query_row("SELECT COUNT(*) FROM memory_event_tags", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tags, 0);
+    }
+
+    #[test]
+    fn test_memory_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.db");
+
+        {
+            let store = MemoryStore::new(Some(path.clone())).unwrap();
+            record_plan_generated(&store, "test_user", 3).unwrap();
+        }
+
+        let store = MemoryStore::new(Some(path)).unwrap();
+        let memory = store.get_user_memory("test_user").unwrap();
+        assert_eq!(memory.timeline.len(), 1);
+        assert_eq!(memory.timeline[0].event_type, MemoryEventType::PlanGenerated);
+    }
 }
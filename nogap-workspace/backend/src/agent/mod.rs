@@ -4,18 +4,33 @@
 //! 
 //! Architecture:
 //! - Memory: Persistent timeline of all agent actions
+//! - Semantic Search: Embedding-based retrieval over the timeline
+//! - Gap Analysis: Job description vs. resume skill comparison
 //! - Planner: Goal-driven career roadmap generation
+//! - Plan Versions: Roadmap history with authors and diffs
 //! - Reflection: Weekly analysis and adaptation
-//! - Resume Parser: External Python integration for PDF/DOCX parsing
+//! - LLM: Pluggable text generation (OpenAI-compatible, Ollama, mock)
+//! - Native Parser: In-process PDF/DOCX/TXT text and field extraction
+//! - Resume Parser: Pipeline stages (native parsing + external Python analysis)
 
+pub mod gap_analysis;
+pub mod llm;
 pub mod memory;
+pub mod native_parser;
+pub mod plan_versions;
 pub mod planner;
 pub mod reflection;
 pub mod resume_parser;
+pub mod semantic_search;
 pub mod types;
 
+pub use gap_analysis::*;
+pub use llm::*;
 pub use memory::*;
+pub use native_parser::*;
+pub use plan_versions::*;
 pub use planner::*;
 pub use reflection::*;
 pub use resume_parser::*;
+pub use semantic_search::*;
 pub use types::*;
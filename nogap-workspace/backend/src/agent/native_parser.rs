@@ -0,0 +1,773 @@
+//! Native Resume Parsing
+//!
+//! Extracts text from PDF, DOCX and plain-text resumes in-process and pulls
+//! out skills, roles, dates and education with simple keyword heuristics.
+//! Produces the same `ParsedResume` the rest of the pipeline consumes, so
+//! parsing needs no Python runtime. The evidence, bottleneck and strategy
+//! stages (`resume_parser::analyze_parsed`) still run the Python scripts.
+
+use super::resume_parser::{ParsedEducation, ParsedExperience, ParsedResume, ResumeParseError};
+use chrono::Datelike;
+use std::io::Read;
+use std::path::Path;
+
+/// Largest resume accepted for parsing (5 MiB)
+pub const MAX_RESUME_BYTES: usize = 5 * 1024 * 1024;
+
+/// Largest decompressed DOCX body we will read, so a zip bomb cannot exhaust memory
+pub const MAX_XML_BYTES: usize = 20 * 1024 * 1024;
+
+/// Maximum skills / experience / education entries returned
+const MAX_SKILLS: usize = 50;
+const MAX_EXPERIENCE: usize = 10;
+const MAX_EDUCATION: usize = 5;
+
+// ============================================================
+// DOCUMENT FORMAT
+// ============================================================
+
+/// Supported resume file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeFormat {
+    Pdf,
+    Docx,
+    Text,
+}
+
+impl ResumeFormat {
+    /// Determine the format from a filename extension
+    pub fn from_extension(filename: &str) -> Option<Self> {
+        let ext = Path::new(filename)
+            .extension()?
+            .to_str()?
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "pdf" => Some(ResumeFormat::Pdf),
+            "docx" => Some(ResumeFormat::Docx),
+            "txt" => Some(ResumeFormat::Text),
+            _ => None,
+        }
+    }
+
+    /// Determine the format from a MIME type
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/pdf" => Some(ResumeFormat::Pdf),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                Some(ResumeFormat::Docx)
+            }
+            "text/plain" => Some(ResumeFormat::Text),
+            _ => None,
+        }
+    }
+
+    /// Check that the file content actually looks like this format
+    pub fn matches_content(&self, bytes: &[u8]) -> bool {
+        match self {
+            ResumeFormat::Pdf => bytes.starts_with(b"%PDF-"),
+            ResumeFormat::Docx => bytes.starts_with(b"PK\x03\x04"),
+            ResumeFormat::Text => std::str::from_utf8(bytes).is_ok(),
+        }
+    }
+}
+
+/// Validate an uploaded resume and work out its format.
+///
+/// The declared type (content type, falling back to the filename) must be
+/// supported, the size must be within `MAX_RESUME_BYTES`, and the bytes must
+/// match the declared format.
+pub fn validate_upload(
+    bytes: &[u8],
+    filename: Option<&str>,
+    content_type: Option<&str>,
+) -> Result<ResumeFormat, ResumeParseError> {
+    if bytes.is_empty() {
+        return Err(ResumeParseError::InvalidUpload("empty file".to_string()));
+    }
+    if bytes.len() > MAX_RESUME_BYTES {
+        return Err(ResumeParseError::InvalidUpload(format!(
+            "file is {} bytes, limit is {} bytes",
+            bytes.len(),
+            MAX_RESUME_BYTES
+        )));
+    }
+
+    let format = content_type
+        .and_then(ResumeFormat::from_content_type)
+        .or_else(|| filename.and_then(ResumeFormat::from_extension))
+        .ok_or_else(|| {
+            ResumeParseError::InvalidUpload(
+                "unsupported file type. Use PDF, DOCX, or TXT.".to_string(),
+            )
+        })?;
+
+    if !format.matches_content(bytes) {
+        return Err(ResumeParseError::InvalidUpload(format!(
+            "file content does not match declared type {:?}",
+            format
+        )));
+    }
+
+    Ok(format)
+}
+
+// ============================================================
+// TEXT EXTRACTION
+// ============================================================
+
+/// Extract plain text from a resume document
+pub fn extract_text(bytes: &[u8], format: ResumeFormat) -> Result<String, ResumeParseError> {
+    match format {
+        ResumeFormat::Pdf => pdf_extract::extract_text_from_mem(bytes)
+            .map_err(|e| ResumeParseError::ParserError(format!("PDF extraction failed: {}", e))),
+        ResumeFormat::Docx => extract_docx_text(bytes),
+        ResumeFormat::Text => Ok(String::from_utf8_lossy(bytes).into_owned()),
+    }
+}
+
+/// Read `word/document.xml` from a DOCX archive and flatten it to text
+fn extract_docx_text(bytes: &[u8]) -> Result<String, ResumeParseError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| ResumeParseError::ParserError(format!("Invalid DOCX archive: {}", e)))?;
+    let mut xml = Vec::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| ResumeParseError::ParserError(format!("DOCX has no document body: {}", e)))?
+        .take(MAX_XML_BYTES as u64 + 1)
+        .read_to_end(&mut xml)
+        .map_err(|e| ResumeParseError::ParserError(format!("Failed to read DOCX body: {}", e)))?;
+    if xml.len() > MAX_XML_BYTES {
+        return Err(ResumeParseError::InvalidUpload(format!(
+            "DOCX body exceeds {} bytes when decompressed",
+            MAX_XML_BYTES
+        )));
+    }
+    let xml = String::from_utf8(xml)
+        .map_err(|e| ResumeParseError::ParserError(format!("DOCX body is not UTF-8: {}", e)))?;
+
+    Ok(docx_xml_to_text(&xml))
+}
+
+/// Flatten WordprocessingML to text: one line per paragraph, tabs and breaks kept
+fn docx_xml_to_text(xml: &str) -> String {
+    let mut text = String::new();
+    let mut rest = xml;
+    let mut in_text_run = false;
+    let mut in_tab_stops = false;
+
+    while let Some(start) = rest.find('<') {
+        if in_text_run {
+            text.push_str(&decode_xml_entities(&rest[..start]));
+        }
+        let end = match rest[start..].find('>') {
+            Some(e) => start + e,
+            None => break,
+        };
+        let tag = &rest[start + 1..end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("");
+
+        match name {
+            "w:t" => in_text_run = !tag.starts_with('/') && !tag.ends_with('/'),
+            "w:tabs" => in_tab_stops = !tag.starts_with('/'),
+            "w:tab" if !in_tab_stops => text.push('\t'),
+            "w:br" | "w:cr" => text.push('\n'),
+            "w:p" if tag.starts_with('/') => text.push('\n'),
+            _ => {}
+        }
+        rest = &rest[end + 1..];
+    }
+
+    text
+}
+
+fn decode_xml_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// ============================================================
+// STRUCTURED EXTRACTION
+// ============================================================
+
+/// Skill keywords recognised in resumes (mirrors the former Python patterns)
+const SKILL_KEYWORDS: &[&str] = &[
+    "Python", "Java", "JavaScript", "TypeScript", "C++", "C#", "Ruby", "Go", "Rust", "Swift",
+    "Kotlin", "PHP", "Scala", "R", "MATLAB",
+    "React", "Angular", "Vue", "Node.js", "Django", "Flask", "Spring", "Express", "Rails",
+    "Laravel", ".NET",
+    "AWS", "Azure", "GCP", "Docker", "Kubernetes", "Jenkins", "Git", "Linux", "SQL", "NoSQL",
+    "MongoDB", "PostgreSQL", "MySQL",
+    "Machine Learning", "Deep Learning", "NLP", "Computer Vision", "TensorFlow", "PyTorch",
+    "Scikit-learn",
+    "HTML", "CSS", "REST", "GraphQL", "API", "Microservices", "Agile", "Scrum", "CI/CD", "DevOps",
+    "Excel", "PowerPoint", "Tableau", "Power BI", "Jira", "Confluence",
+];
+
+/// Words that mark a line fragment as a job title
+const ROLE_KEYWORDS: &[&str] = &[
+    "engineer", "developer", "manager", "analyst", "intern", "scientist", "designer",
+    "consultant", "lead", "architect", "administrator", "specialist", "coordinator", "director",
+    "associate", "programmer", "researcher", "assistant", "officer", "technician",
+];
+
+/// Degree markers, matched case-insensitively on word boundaries
+const DEGREE_KEYWORDS: &[&str] = &[
+    "bachelor", "master", "phd", "ph.d", "b.s.", "m.s.", "b.a.", "m.a.", "mba", "b.tech",
+    "m.tech", "b.e.", "m.e.", "b.sc", "m.sc", "associate degree", "diploma",
+];
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parse a resume file from disk
+pub fn parse_resume_file(path: &Path) -> Result<ParsedResume, ResumeParseError> {
+    let filename = path.to_string_lossy();
+    let format = ResumeFormat::from_extension(&filename).ok_or_else(|| {
+        ResumeParseError::InvalidUpload(format!(
+            "unsupported file type: {}. Use PDF, DOCX, or TXT.",
+            filename
+        ))
+    })?;
+    let bytes = std::fs::read(path)
+        .map_err(|_| ResumeParseError::FileNotFound(filename.to_string()))?;
+    parse_resume_bytes(&bytes, format)
+}
+
+/// Parse an in-memory resume document
+pub fn parse_resume_bytes(
+    bytes: &[u8],
+    format: ResumeFormat,
+) -> Result<ParsedResume, ResumeParseError> {
+    let raw_text = extract_text(bytes, format)?;
+    Ok(parse_resume_text(&raw_text))
+}
+
+/// Extract structured data from resume text
+pub fn parse_resume_text(text: &str) -> ParsedResume {
+    let text = text.trim();
+    if text.is_empty() {
+        return ParsedResume {
+            skills: Vec::new(),
+            experience: Vec::new(),
+            education: Vec::new(),
+            total_experience: None,
+            raw_text: String::new(),
+        };
+    }
+
+    let experience = extract_experience(text);
+    let total_experience = stated_years_of_experience(text).or_else(|| {
+        experience_span_years(&experience)
+    });
+
+    ParsedResume {
+        skills: extract_skills(text),
+        experience,
+        education: extract_education(text),
+        total_experience,
+        raw_text: text.to_string(),
+    }
+}
+
+/// Find known skills, preserving their canonical spelling
+pub fn extract_skills(text: &str) -> Vec<String> {
+    let lower = text.to_lowercase();
+    SKILL_KEYWORDS
+        .iter()
+        .filter(|skill| {
+            // One- and two-letter skills ("R", "Go") only count with exact casing
+            if skill.len() <= 2 {
+                contains_word(text, skill)
+            } else {
+                contains_word(&lower, &skill.to_lowercase())
+            }
+        })
+        .take(MAX_SKILLS)
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Find job entries: any line carrying a date range is treated as a role header
+pub fn extract_experience(text: &str) -> Vec<ParsedExperience> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let mut experience = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(range) = find_date_range(line) else {
+            continue;
+        };
+        if is_education_line(line) {
+            continue;
+        }
+
+        let header = strip_span(line, &range.span);
+        let (mut role, mut company) = split_role_and_company(&header);
+
+        // "Acme Corp" on one line, "Engineer  Jan 2020 - Present" on the next
+        if company.is_none() && i > 0 {
+            let previous = lines[i - 1];
+            if !previous.is_empty() && find_date_range(previous).is_none() {
+                if role.is_none() && looks_like_role(previous) {
+                    role = Some(previous.to_string());
+                } else if !looks_like_role(previous) {
+                    company = Some(previous.to_string());
+                }
+            }
+        }
+
+        experience.push(ParsedExperience {
+            company: company.unwrap_or_default(),
+            role,
+            start_date: Some(range.start.to_string()),
+            end_date: Some(range.end.to_string()),
+        });
+        if experience.len() >= MAX_EXPERIENCE {
+            break;
+        }
+    }
+
+    experience
+}
+
+/// Find degree lines, with institution and graduation year where present
+pub fn extract_education(text: &str) -> Vec<ParsedEducation> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let mut education = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if !is_education_line(line) {
+            continue;
+        }
+
+        let parts: Vec<&str> = line
+            .split([',', '|'])
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+        let degree = parts.iter().find(|p| is_education_line(p)).copied();
+        let institution = parts
+            .iter()
+            .find(|p| is_institution(p))
+            .copied()
+            .or_else(|| lines.get(i + 1).copied().filter(|l| is_institution(l)))
+            .or_else(|| {
+                i.checked_sub(1)
+                    .and_then(|p| lines.get(p).copied())
+                    .filter(|l| is_institution(l))
+            });
+
+        education.push(ParsedEducation {
+            degree: degree.map(strip_years),
+            institution: institution.map(strip_years),
+            year: years_in(line).last().copied(),
+        });
+        if education.len() >= MAX_EDUCATION {
+            break;
+        }
+    }
+
+    education
+}
+
+/// "5+ years of experience" style statements
+fn stated_years_of_experience(text: &str) -> Option<f64> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    for (i, word) in words.iter().enumerate() {
+        let number = word.trim_end_matches('+');
+        let Some(years) = number.parse::<f64>().ok().filter(|y| y.is_finite()) else {
+            continue;
+        };
+        let unit = words.get(i + 1).copied().unwrap_or("");
+        if !unit.starts_with("year") && !unit.starts_with("yr") {
+            continue;
+        }
+        let tail = words[i + 2..words.len().min(i + 5)].join(" ");
+        if tail.contains("experience")
+            || tail.contains("software")
+            || tail.contains("development")
+            || tail.contains("engineering")
+        {
+            return Some(years);
+        }
+    }
+
+    None
+}
+
+/// Years between the earliest start and the latest end of all roles
+fn experience_span_years(experience: &[ParsedExperience]) -> Option<f64> {
+    let months = |s: &Option<String>| s.as_deref().and_then(DatePoint::parse).map(|d| d.months());
+    let start = experience.iter().filter_map(|e| months(&e.start_date)).min()?;
+    let end = experience.iter().filter_map(|e| months(&e.end_date)).max()?;
+    (end > start).then(|| ((end - start) as f64 / 12.0 * 10.0).round() / 10.0)
+}
+
+// ============================================================
+// DATE HANDLING
+// ============================================================
+
+/// A month/year point in a resume, or "Present"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DatePoint {
+    YearMonth(i32, Option<u32>),
+    Present,
+}
+
+impl DatePoint {
+    /// Parse "2020", "Jan 2020", "January 2020", "01/2020", "2020-01", "Present"
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        if matches!(s.as_str(), "present" | "current" | "now" | "today") {
+            return Some(DatePoint::Present);
+        }
+        if let Some((y, m)) = s.split_once('-') {
+            let month: u32 = m.parse().ok().filter(|m| (1..=12).contains(m))?;
+            return parse_year(y).map(|y| DatePoint::YearMonth(y, Some(month)));
+        }
+        if let Some((m, y)) = s.split_once('/') {
+            let month: u32 = m.parse().ok().filter(|m| (1..=12).contains(m))?;
+            return parse_year(y).map(|y| DatePoint::YearMonth(y, Some(month)));
+        }
+        if let Some((m, y)) = s.split_once(' ') {
+            let month = month_number(m)?;
+            return parse_year(y).map(|y| DatePoint::YearMonth(y, Some(month)));
+        }
+        parse_year(&s).map(|y| DatePoint::YearMonth(y, None))
+    }
+
+    /// Months since year 0 (Present = this month)
+    fn months(&self) -> i64 {
+        match self {
+            DatePoint::YearMonth(y, m) => *y as i64 * 12 + m.unwrap_or(1) as i64 - 1,
+            DatePoint::Present => {
+                let now = chrono::Utc::now();
+                now.year() as i64 * 12 + now.month0() as i64
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DatePoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatePoint::YearMonth(y, Some(m)) => write!(f, "{:04}-{:02}", y, m),
+            DatePoint::YearMonth(y, None) => write!(f, "{:04}", y),
+            DatePoint::Present => write!(f, "present"),
+        }
+    }
+}
+
+/// A "start - end" range found in a line, with its byte span
+#[derive(Debug)]
+struct DateRange {
+    start: DatePoint,
+    end: DatePoint,
+    span: std::ops::Range<usize>,
+}
+
+/// Find the first "start - end" / "start to end" date range in a line
+fn find_date_range(line: &str) -> Option<DateRange> {
+    let tokens = tokenize_with_offsets(line);
+
+    for i in 0..tokens.len() {
+        // A date point is one token ("2020", "01/2020", "present") or two ("jan 2020")
+        let Some((start, start_len)) = date_point_at(&tokens, i) else {
+            continue;
+        };
+        let sep = i + start_len;
+        let is_separator = tokens
+            .get(sep)
+            .map(|(t, _)| matches!(t.to_lowercase().as_str(), "-" | "to" | "until"))
+            .unwrap_or(false);
+        if !is_separator {
+            continue;
+        }
+        let Some((end, end_len)) = date_point_at(&tokens, sep + 1) else {
+            continue;
+        };
+        if start == DatePoint::Present {
+            continue;
+        }
+
+        let (_, first) = &tokens[i];
+        let (last_token, last) = &tokens[sep + end_len];
+        return Some(DateRange {
+            start,
+            end,
+            span: first.start..last.start + last_token.len(),
+        });
+    }
+
+    None
+}
+
+fn date_point_at(
+    tokens: &[(&str, std::ops::Range<usize>)],
+    i: usize,
+) -> Option<(DatePoint, usize)> {
+    let (first, _) = tokens.get(i)?;
+    if let Some((second, _)) = tokens.get(i + 1) {
+        if month_number(first).is_some() {
+            if let Some(point) = DatePoint::parse(&format!("{} {}", first, second)) {
+                return Some((point, 2));
+            }
+        }
+    }
+    DatePoint::parse(first).map(|p| (p, 1))
+}
+
+/// Split a line into tokens, treating any dash as its own token except the
+/// one inside an ISO "2020-01" year-month
+fn tokenize_with_offsets(line: &str) -> Vec<(&str, std::ops::Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (idx, c) in line.char_indices() {
+        let in_year_month = c == '-'
+            && start.is_some_and(|s| parse_year(&line[s..idx]).is_some())
+            && is_month_digits(&line[idx + 1..]);
+        let is_dash = matches!(c, '-' | '–' | '—') && !in_year_month;
+        let is_break = c.is_whitespace() || matches!(c, ',' | '(' | ')' | '|' | '•' | '·');
+        if is_dash || is_break {
+            if let Some(s) = start.take() {
+                tokens.push((&line[s..idx], s..idx));
+            }
+            if is_dash {
+                tokens.push(("-", idx..idx + c.len_utf8()));
+            }
+        } else if start.is_none() {
+            start = Some(idx);
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((&line[s..], s..line.len()));
+    }
+
+    tokens
+}
+
+/// Whether `rest` starts with a one- or two-digit month that ends the number
+fn is_month_digits(rest: &str) -> bool {
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    (1..=2).contains(&digits)
+        && rest[..digits].parse::<u32>().is_ok_and(|m| (1..=12).contains(&m))
+}
+
+fn month_number(s: &str) -> Option<u32> {
+    let s = s.trim_end_matches('.').to_lowercase();
+    if s.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| s.starts_with(m))
+        .filter(|_| {
+            // Reject words that merely begin like a month ("mayor", "marketing")
+            let full = [
+                "january", "february", "march", "april", "may", "june", "july", "august",
+                "september", "sept", "october", "november", "december",
+            ];
+            s.len() == 3 || full.contains(&s.as_str())
+        })
+        .map(|p| p as u32 + 1)
+}
+
+fn parse_year(s: &str) -> Option<i32> {
+    let s = s.trim().trim_start_matches('\'');
+    if s.len() != 4 || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok().filter(|y| (1950..=2100).contains(y))
+}
+
+fn years_in(line: &str) -> Vec<u32> {
+    tokenize_with_offsets(line)
+        .into_iter()
+        .filter_map(|(t, _)| parse_year(t.trim_matches(|c: char| !c.is_ascii_digit())))
+        .map(|y| y as u32)
+        .collect()
+}
+
+// ============================================================
+// TEXT HELPERS
+// ============================================================
+
+/// Whole-word (or whole-phrase) containment; `needle` may contain symbols
+pub(crate) fn contains_word(haystack: &str, needle: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric();
+    haystack.match_indices(needle).any(|(idx, _)| {
+        let before = haystack[..idx].chars().next_back();
+        let after = haystack[idx + needle.len()..].chars().next();
+        let starts_word = needle.starts_with(|c: char| !is_word_char(c));
+        let ends_word = needle.ends_with(|c: char| !is_word_char(c));
+        (starts_word || !before.map(is_word_char).unwrap_or(false))
+            && (ends_word || !after.map(is_word_char).unwrap_or(false))
+    })
+}
+
+fn looks_like_role(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    ROLE_KEYWORDS.iter().any(|k| contains_word(&lower, k))
+}
+
+fn is_education_line(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    DEGREE_KEYWORDS.iter().any(|k| contains_word(&lower, k))
+}
+
+fn is_institution(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    ["university", "college", "institute", "school", "academy"]
+        .iter()
+        .any(|k| lower.contains(k))
+}
+
+/// Remove a byte span from a line and tidy the leftover separators
+fn strip_span(line: &str, span: &std::ops::Range<usize>) -> String {
+    let mut out = String::with_capacity(line.len());
+    out.push_str(&line[..span.start]);
+    out.push(' ');
+    out.push_str(&line[span.end..]);
+    tidy(&out)
+}
+
+fn strip_years(s: &str) -> String {
+    let kept: Vec<&str> = s
+        .split_whitespace()
+        .filter(|w| parse_year(w.trim_matches(|c: char| !c.is_ascii_digit())).is_none())
+        .collect();
+    tidy(&kept.join(" "))
+}
+
+fn tidy(s: &str) -> String {
+    s.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, ',' | '|' | '-' | '–' | '—' | '(' | ')' | ':'))
+        .to_string()
+}
+
+/// Split "Engineer at Acme", "Acme | Engineer", "Engineer, Acme" into (role, company)
+fn split_role_and_company(header: &str) -> (Option<String>, Option<String>) {
+    if header.is_empty() {
+        return (None, None);
+    }
+
+    let parts: Vec<String> = if let Some((role, company)) = header.split_once(" at ") {
+        vec![role.to_string(), company.to_string()]
+    } else if let Some((role, company)) = header.split_once(" @ ") {
+        vec![role.to_string(), company.to_string()]
+    } else {
+        header
+            .split(['|', ',', '–', '—'])
+            .flat_map(|p| p.split(" - "))
+            .map(tidy)
+            .filter(|p| !p.is_empty())
+            .collect()
+    };
+
+    let mut role = None;
+    let mut company = None;
+    for part in parts.into_iter().map(|p| tidy(&p)).filter(|p| !p.is_empty()) {
+        if role.is_none() && looks_like_role(&part) {
+            role = Some(part);
+        } else if company.is_none() {
+            company = Some(part);
+        }
+    }
+
+    (role, company)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Jane Doe\n\
+        jane@example.com\n\
+        Software Engineer with 5 years of experience in Rust and Python.\n\
+        \n\
+        EXPERIENCE\n\
+        Senior Software Engineer at Acme Corp  Jan 2021 - Present\n\
+        Globex\n\
+        Backend Developer | 06/2018 – 12/2020\n\
+        \n\
+        EDUCATION\n\
+        B.Tech in Computer Science, State University, 2018\n\
+        \n\
+        SKILLS\n\
+        Docker, Kubernetes, PostgreSQL, CI/CD, Node.js, Go\n";
+
+    #[test]
+    fn test_extracts_skills_roles_and_dates() {
+        let parsed = parse_resume_text(SAMPLE);
+
+        for skill in ["Rust", "Python", "Docker", "Kubernetes", "PostgreSQL", "CI/CD", "Node.js", "Go"] {
+            assert!(parsed.skills.contains(&skill.to_string()), "missing {}", skill);
+        }
+        assert!(!parsed.skills.contains(&"R".to_string()));
+
+        assert_eq!(parsed.experience.len(), 2);
+        assert_eq!(parsed.experience[0].role.as_deref(), Some("Senior Software Engineer"));
+        assert_eq!(parsed.experience[0].company, "Acme Corp");
+        assert_eq!(parsed.experience[0].start_date.as_deref(), Some("2021-01"));
+        assert_eq!(parsed.experience[0].end_date.as_deref(), Some("present"));
+        assert_eq!(parsed.experience[1].role.as_deref(), Some("Backend Developer"));
+        assert_eq!(parsed.experience[1].company, "Globex");
+        assert_eq!(parsed.experience[1].start_date.as_deref(), Some("2018-06"));
+
+        assert_eq!(parsed.education.len(), 1);
+        assert_eq!(parsed.education[0].degree.as_deref(), Some("B.Tech in Computer Science"));
+        assert_eq!(parsed.education[0].institution.as_deref(), Some("State University"));
+        assert_eq!(parsed.education[0].year, Some(2018));
+
+        assert_eq!(parsed.total_experience, Some(5.0));
+    }
+
+    #[test]
+    fn test_iso_dates_and_non_finite_years() {
+        let parsed = parse_resume_text(
+            "EXPERIENCE\nData Engineer at Initech 2019-03 - 2021-11\nAnalyst at Hooli 2016-2018\n",
+        );
+        assert_eq!(parsed.experience.len(), 2);
+        assert_eq!(parsed.experience[0].start_date.as_deref(), Some("2019-03"));
+        assert_eq!(parsed.experience[0].end_date.as_deref(), Some("2021-11"));
+        assert_eq!(parsed.experience[1].start_date.as_deref(), Some("2016"));
+        assert_eq!(parsed.experience[1].end_date.as_deref(), Some("2018"));
+
+        assert_eq!(stated_years_of_experience("inf years of experience"), None);
+        assert_eq!(stated_years_of_experience("NaN years of software experience"), None);
+    }
+
+    #[test]
+    fn test_docx_xml_to_text() {
+        let xml = r#"<w:document><w:body><w:p><w:r><w:t>Rust &amp; Go</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">Engineer</w:t><w:tab/><w:t>2020</w:t></w:r></w:p></w:body></w:document>"#;
+        assert_eq!(docx_xml_to_text(xml), "Rust & Go\nEngineer\t2020\n");
+    }
+
+    #[test]
+    fn test_validate_upload() {
+        assert_eq!(
+            validate_upload(b"%PDF-1.7 ...", Some("cv.pdf"), None).unwrap(),
+            ResumeFormat::Pdf
+        );
+        assert_eq!(
+            validate_upload(b"plain text", None, Some("text/plain; charset=utf-8")).unwrap(),
+            ResumeFormat::Text
+        );
+        assert!(validate_upload(b"not a pdf", Some("cv.pdf"), None).is_err());
+        assert!(validate_upload(b"MZ...", Some("cv.exe"), None).is_err());
+        assert!(validate_upload(&vec![b'a'; MAX_RESUME_BYTES + 1], Some("cv.txt"), None).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! Plan Versioning Module
+//!
+//! Keeps every version of a user's career roadmap so human-in-the-loop
+//! edits can be reviewed: who changed the plan, when, and what changed
+//! between any two versions.
+
+use super::planner::{CareerRoadmap, RoadmapEdit, RoadmapStep};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Author recorded for versions produced by the planner itself
+pub const AGENT_AUTHOR: &str = "agent";
+
+// ============================================================
+// VERSION STRUCTURES
+// ============================================================
+
+/// A saved snapshot of a roadmap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanVersion {
+    pub id: String,
+    pub user_id: String,
+    /// 1 for the first saved plan, incremented on every change
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Who made the change: a username, a user ID, or "agent"
+    pub author: String,
+    /// Human-readable description of the change
+    pub change: String,
+    /// The edit that produced this version (None for generated plans)
+    pub edit: Option<RoadmapEdit>,
+    pub roadmap: CareerRoadmap,
+}
+
+/// Version metadata without the roadmap body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanVersionSummary {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub author: String,
+    pub change: String,
+    pub step_count: usize,
+}
+
+impl PlanVersion {
+    pub fn summary(&self) -> PlanVersionSummary {
+        PlanVersionSummary {
+            version: self.version,
+            created_at: self.created_at,
+            author: self.author.clone(),
+            change: self.change.clone(),
+            step_count: self.roadmap.steps.len(),
+        }
+    }
+}
+
+// ============================================================
+// DIFF STRUCTURES
+// ============================================================
+
+/// A step as shown in a diff
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepRef {
+    pub step_id: String,
+    pub title: String,
+    pub order: u32,
+}
+
+/// A step whose position changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepMove {
+    pub step_id: String,
+    pub title: String,
+    pub from_order: u32,
+    pub to_order: u32,
+}
+
+/// One changed field of a step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A step whose details changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepChange {
+    pub step_id: String,
+    pub title: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences between two roadmap versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanDiff {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub added: Vec<StepRef>,
+    pub removed: Vec<StepRef>,
+    pub moved: Vec<StepMove>,
+    pub changed: Vec<StepChange>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two versions step by step (steps are matched by ID)
+pub fn diff_versions(from: &PlanVersion, to: &PlanVersion) -> PlanDiff {
+    let find = |steps: &[RoadmapStep], id: &str| steps.iter().find(|s| s.id == id).cloned();
+    let step_ref = |s: &RoadmapStep| StepRef {
+        step_id: s.id.clone(),
+        title: s.title.clone(),
+        order: s.order,
+    };
+
+    let old_steps = &from.roadmap.steps;
+    let new_steps = &to.roadmap.steps;
+
+    let removed = old_steps
+        .iter()
+        .filter(|s| find(new_steps, &s.id).is_none())
+        .map(step_ref)
+        .collect();
+    let added = new_steps
+        .iter()
+        .filter(|s| find(old_steps, &s.id).is_none())
+        .map(step_ref)
+        .collect();
+
+    let moved_ids = moved_step_ids(old_steps, new_steps);
+    let mut moved = Vec::new();
+    let mut changed = Vec::new();
+    for new in new_steps {
+        let old = match find(old_steps, &new.id) {
+            Some(old) => old,
+            None => continue,
+        };
+
+        if moved_ids.contains(&new.id) {
+            moved.push(StepMove {
+                step_id: new.id.clone(),
+                title: new.title.clone(),
+                from_order: old.order,
+                to_order: new.order,
+            });
+        }
+
+        let mut changes = Vec::new();
+        let mut compare = |field: &str, a: String, b: String| {
+            if a != b {
+                changes.push(FieldChange {
+                    field: field.to_string(),
+                    from: a,
+                    to: b,
+                });
+            }
+        };
+        compare("title", old.title.clone(), new.title.clone());
+        compare("description", old.description.clone(), new.description.clone());
+        compare("step_type", old.step_type.clone(), new.step_type.clone());
+        compare("estimated_weeks", old.estimated_weeks.to_string(), new.estimated_weeks.to_string());
+        compare("status", old.status.clone(), new.status.clone());
+        if !changes.is_empty() {
+            changed.push(StepChange {
+                step_id: new.id.clone(),
+                title: new.title.clone(),
+                changes,
+            });
+        }
+    }
+
+    PlanDiff {
+        from_version: from.version,
+        to_version: to.version,
+        added,
+        removed,
+        moved,
+        changed,
+    }
+}
+
+/// The fewest shared steps that explain the new order: every step outside a
+/// longest run that kept its relative order. Shifts caused by adds and
+/// removes, and steps a moved step jumped over, are not reported.
+fn moved_step_ids(old: &[RoadmapStep], new: &[RoadmapStep]) -> HashSet<String> {
+    let old_positions: HashMap<&str, usize> =
+        old.iter().enumerate().map(|(i, s)| (s.id.as_str(), i)).collect();
+    // Old positions of the shared steps, in their new order
+    let shared: Vec<(&str, usize)> = new
+        .iter()
+        .filter_map(|s| old_positions.get(s.id.as_str()).map(|&p| (s.id.as_str(), p)))
+        .collect();
+
+    // Longest increasing subsequence (patience sorting with back links)
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; shared.len()];
+    for (i, &(_, position)) in shared.iter().enumerate() {
+        let slot = tails.partition_point(|&t| shared[t].1 < position);
+        previous[i] = slot.checked_sub(1).map(|s| tails[s]);
+        if slot == tails.len() {
+            tails.push(i);
+        } else {
+            tails[slot] = i;
+        }
+    }
+    let mut in_order = HashSet::new();
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        in_order.insert(i);
+        cursor = previous[i];
+    }
+
+    shared
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !in_order.contains(i))
+        .map(|(_, (id, _))| id.to_string())
+        .collect()
+}
+
+// ============================================================
+// VERSION STORE
+// ============================================================
+
+/// SQLite-backed roadmap history (shares the memory database)
+pub struct PlanVersionStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PlanVersionStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> SqlResult<Self> {
+        conn.lock().unwrap().execute(
+            "CREATE TABLE IF NOT EXISTS plan_versions (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                author TEXT NOT NULL,
+                change TEXT NOT NULL,
+                edit TEXT,
+                roadmap TEXT NOT NULL,
+                UNIQUE (user_id, version)
+            )",
+            [],
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Save the roadmap as the user's next version
+    pub fn save(
+        &self,
+        user_id: &str,
+        author: &str,
+        change: &str,
+        edit: Option<&RoadmapEdit>,
+        roadmap: &CareerRoadmap,
+    ) -> SqlResult<PlanVersion> {
+        let conn = self.conn.lock().unwrap();
+        let latest: Option<u32> = conn.query_row(
+            "SELECT MAX(version) FROM plan_versions WHERE user_id = ?1",
+            [user_id],
+            |row| row.get(0),
+        )?;
+
+        let version = PlanVersion {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            version: latest.unwrap_or(0) + 1,
+            created_at: Utc::now(),
+            author: author.to_string(),
+            change: change.to_string(),
+            edit: edit.cloned(),
+            roadmap: roadmap.clone(),
+        };
+        conn.execute(
+            "INSERT INTO plan_versions (id, user_id, version, created_at, author, change, edit, roadmap)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                version.id,
+                version.user_id,
+                version.version,
+                version.created_at.to_rfc3339(),
+                version.author,
+                version.change,
+                version.edit.as_ref().map(|e| serde_json::to_string(e).unwrap_or_default()),
+                serde_json::to_string(&version.roadmap).unwrap_or_default(),
+            ],
+        )?;
+
+        Ok(version)
+    }
+
+    /// All versions for a user, oldest first
+    pub fn list(&self, user_id: &str) -> SqlResult<Vec<PlanVersionSummary>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM plan_versions WHERE user_id = ?1 ORDER BY version ASC",
+            VERSION_COLUMNS
+        ))?;
+        let versions = stmt.query_map([user_id], row_to_version)?;
+        Ok(versions.filter_map(|v| v.ok()).map(|v| v.summary()).collect())
+    }
+
+    pub fn get(&self, user_id: &str, version: u32) -> SqlResult<Option<PlanVersion>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM plan_versions WHERE user_id = ?1 AND version = ?2",
+                VERSION_COLUMNS
+            ),
+            params![user_id, version],
+            row_to_version,
+        )
+        .optional()
+    }
+
+    pub fn latest(&self, user_id: &str) -> SqlResult<Option<PlanVersion>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM plan_versions WHERE user_id = ?1 ORDER BY version DESC LIMIT 1",
+                VERSION_COLUMNS
+            ),
+            [user_id],
+            row_to_version,
+        )
+        .optional()
+    }
+
+    /// The newest roadmap of every user, used to restore plans after a restart
+    pub fn latest_roadmaps(&self) -> SqlResult<Vec<CareerRoadmap>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM plan_versions v
+             WHERE version = (SELECT MAX(version) FROM plan_versions WHERE user_id = v.user_id)",
+            VERSION_COLUMNS
+        ))?;
+        let versions = stmt.query_map([], row_to_version)?;
+        Ok(versions.filter_map(|v| v.ok()).map(|v| v.roadmap).collect())
+    }
+}
+
+const VERSION_COLUMNS: &str = "id, user_id, version, created_at, author, change, edit, roadmap";
+
+fn row_to_version(row: &rusqlite::Row) -> SqlResult<PlanVersion> {
+    let created_at: String = row.get(3)?;
+    let edit: Option<String> = row.get(6)?;
+    let roadmap: String = row.get(7)?;
+
+    Ok(PlanVersion {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        version: row.get(2)?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        author: row.get(4)?,
+        change: row.get(5)?,
+        edit: edit.and_then(|e| serde_json::from_str(&e).ok()),
+        roadmap: serde_json::from_str(&roadmap).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::planner::{apply_edit, CareerPlanner, PlannerConfig, PlannerInput};
+    use crate::agent::types::{CareerGoal, CareerRule, ResumeData};
+    use std::collections::HashSet;
+
+    fn test_roadmap() -> CareerRoadmap {
+        CareerPlanner::new(PlannerConfig::default()).generate_roadmap(&PlannerInput {
+            resume: ResumeData {
+                user_id: "test_user".to_string(),
+                ..Default::default()
+            },
+            assessments: Vec::new(),
+            goal: CareerGoal::new("test_user", "Test Goal"),
+            available_rules: vec![
+                CareerRule::new("step1", "First Step", "technical_skill"),
+                CareerRule::new("step2", "Second Step", "technical_skill"),
+                CareerRule::new("step3", "Third Step", "technical_skill"),
+            ],
+            deferred_steps: HashSet::new(),
+        })
+    }
+
+    #[test]
+    fn test_versions_are_numbered_and_persisted() {
+        let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        let store = PlanVersionStore::new(conn).unwrap();
+        let mut roadmap = test_roadmap();
+
+        let v1 = store.save("test_user", AGENT_AUTHOR, "Generated roadmap", None, &roadmap).unwrap();
+        let edit = RoadmapEdit::RemoveStep {
+            step_id: roadmap.steps[0].id.clone(),
+        };
+        let change = apply_edit(&mut roadmap, edit.clone()).unwrap();
+        let v2 = store.save("test_user", "alice", &change, Some(&edit), &roadmap).unwrap();
+
+        assert_eq!((v1.version, v2.version), (1, 2));
+        let history = store.list("test_user").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].author, "alice");
+        assert_eq!(history[1].step_count, 2);
+        assert!(store.get("test_user", 2).unwrap().unwrap().edit.is_some());
+        assert_eq!(store.latest("test_user").unwrap().unwrap().version, 2);
+        assert_eq!(store.latest_roadmaps().unwrap().len(), 1);
+        assert!(store.latest("other_user").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_diff_versions() {
+        let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        let store = PlanVersionStore::new(conn).unwrap();
+        let mut roadmap = test_roadmap();
+        let v1 = store.save("test_user", AGENT_AUTHOR, "Generated roadmap", None, &roadmap).unwrap();
+
+        let first = roadmap.steps[0].id.clone();
+        let second = roadmap.steps[1].id.clone();
+        let third = roadmap.steps[2].id.clone();
+        apply_edit(&mut roadmap, RoadmapEdit::RemoveStep { step_id: first.clone() }).unwrap();
+        apply_edit(
+            &mut roadmap,
+            RoadmapEdit::AddStep {
+                title: "Build a portfolio".to_string(),
+                description: "Ship two projects".to_string(),
+                step_type: "apply".to_string(),
+                after_step_id: None,
+            },
+        )
+        .unwrap();
+        apply_edit(
+            &mut roadmap,
+            RoadmapEdit::Reorder {
+                step_id: third.clone(),
+                new_order: 1,
+            },
+        )
+        .unwrap();
+        apply_edit(
+            &mut roadmap,
+            RoadmapEdit::UpdateStep {
+                step_id: second.clone(),
+                title: None,
+                description: None,
+                estimated_weeks: Some(6),
+            },
+        )
+        .unwrap();
+        let v2 = store.save("test_user", "alice", "Several edits", None, &roadmap).unwrap();
+
+        let diff = diff_versions(&v1, &v2);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].step_id, first);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].title, "Build a portfolio");
+        assert!(diff.moved.iter().any(|m| m.step_id == third));
+        let change = diff.changed.iter().find(|c| c.step_id == second).unwrap();
+        assert_eq!(change.changes[0].field, "estimated_weeks");
+        assert!(diff_versions(&v2, &v2).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_minimal_moves() {
+        let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        let store = PlanVersionStore::new(conn).unwrap();
+        let mut roadmap = test_roadmap();
+        let v1 = store.save("test_user", AGENT_AUTHOR, "Generated roadmap", None, &roadmap).unwrap();
+
+        // [A, B, C] -> [B, C, A]: only A moved
+        let ids: Vec<String> = roadmap.steps.iter().map(|s| s.id.clone()).collect();
+        let edit = RoadmapEdit::SetOrder {
+            step_ids: vec![ids[1].clone(), ids[2].clone(), ids[0].clone()],
+        };
+        apply_edit(&mut roadmap, edit).unwrap();
+        let v2 = store.save("test_user", "alice", "Reordered steps", None, &roadmap).unwrap();
+
+        let diff = diff_versions(&v1, &v2);
+        assert_eq!(diff.moved.len(), 1);
+        assert_eq!(diff.moved[0].step_id, ids[0]);
+        assert_eq!((diff.moved[0].from_order, diff.moved[0].to_order), (1, 3));
+    }
+}
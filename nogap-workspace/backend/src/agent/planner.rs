@@ -492,11 +492,16 @@ impl CareerPlanner {
 // ============================================================
 
 /// Edit operation for a roadmap step
+/// Longest estimate a user can give a single step (ten years)
+pub const MAX_STEP_WEEKS: u32 = 520;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum RoadmapEdit {
     /// Reorder a step
     Reorder { step_id: String, new_order: u32 },
+    /// Reorder all steps at once (e.g. after drag and drop); must list every step ID
+    SetOrder { step_ids: Vec<String> },
     /// Update step details
     UpdateStep { step_id: String, title: Option<String>, description: Option<String>, estimated_weeks: Option<u32> },
     /// Mark step as completed
@@ -524,12 +529,30 @@ pub fn apply_edit(roadmap: &mut CareerRoadmap, edit: RoadmapEdit) -> Result<Stri
             }
             Ok(format!("Reordered step to position {}", new_order))
         }
+        RoadmapEdit::SetOrder { step_ids } => {
+            let current: HashSet<&str> = roadmap.steps.iter().map(|s| s.id.as_str()).collect();
+            let requested: HashSet<&str> = step_ids.iter().map(|s| s.as_str()).collect();
+            if step_ids.len() != roadmap.steps.len() || current != requested {
+                return Err("Step order must list every step exactly once".to_string());
+            }
+            roadmap.steps.sort_by_key(|s| step_ids.iter().position(|id| *id == s.id));
+            // Renumber
+            for (i, s) in roadmap.steps.iter_mut().enumerate() {
+                s.order = (i + 1) as u32;
+            }
+            Ok("Reordered steps".to_string())
+        }
         RoadmapEdit::UpdateStep { step_id, title, description, estimated_weeks } => {
             let step = roadmap.steps.iter_mut().find(|s| s.id == step_id)
                 .ok_or("Step not found")?;
             if let Some(t) = title { step.title = t; }
             if let Some(d) = description { step.description = d; }
-            if let Some(w) = estimated_weeks { step.estimated_weeks = w; }
+            if let Some(w) = estimated_weeks {
+                if !(1..=MAX_STEP_WEEKS).contains(&w) {
+                    return Err(format!("estimated_weeks must be 1-{}", MAX_STEP_WEEKS));
+                }
+                step.estimated_weeks = w;
+            }
             Ok("Step updated".to_string())
         }
         RoadmapEdit::CompleteStep { step_id } => {
@@ -662,5 +685,24 @@ mod tests {
         let result = apply_edit(&mut roadmap, RoadmapEdit::CompleteStep { step_id });
         assert!(result.is_ok());
         assert_eq!(roadmap.steps[0].status, "completed");
+
+        // Test bulk reorder
+        let reversed: Vec<String> = roadmap.steps.iter().rev().map(|s| s.id.clone()).collect();
+        apply_edit(&mut roadmap, RoadmapEdit::SetOrder { step_ids: reversed.clone() }).unwrap();
+        assert_eq!(roadmap.steps[0].id, reversed[0]);
+        assert_eq!(roadmap.steps[0].order, 1);
+        assert!(apply_edit(&mut roadmap, RoadmapEdit::SetOrder { step_ids: vec![reversed[0].clone()] }).is_err());
+
+        // Step estimates are bounded
+        let update = |weeks| RoadmapEdit::UpdateStep {
+            step_id: reversed[0].clone(),
+            title: None,
+            description: None,
+            estimated_weeks: Some(weeks),
+        };
+        assert!(apply_edit(&mut roadmap, update(4_000_000_000)).is_err());
+        assert!(apply_edit(&mut roadmap, update(0)).is_err());
+        apply_edit(&mut roadmap, update(MAX_STEP_WEEKS)).unwrap();
+        assert_eq!(roadmap.steps[0].estimated_weeks, MAX_STEP_WEEKS);
     }
 }
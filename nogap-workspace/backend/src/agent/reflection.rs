@@ -4,10 +4,12 @@
 //! Generates weekly reflections on career progress, plan adaptations,
 //! and next-step suggestions.
 
+use super::llm::{ChatMessage, LlmProvider};
 use super::memory::{CareerMemory, MemoryEvent, MemoryEventType, MemoryStore};
 use super::planner::CareerRoadmap;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 // ============================================================
@@ -76,11 +78,20 @@ impl Default for ReflectionConfig {
 /// Generates weekly reflections
 pub struct ReflectionGenerator {
     config: ReflectionConfig,
+    llm: Option<Arc<dyn LlmProvider>>,
 }
 
 impl ReflectionGenerator {
     pub fn new(config: ReflectionConfig) -> Self {
-        Self { config }
+        Self { config, llm: None }
+    }
+
+    /// Write summaries with an LLM provider (falls back to rule-based text on failure)
+    pub fn with_llm(mut self, provider: Arc<dyn LlmProvider>) -> Self {
+        self.config.use_llm = true;
+        self.config.llm_model = Some(provider.model().to_string());
+        self.llm = Some(provider);
+        self
     }
 
     /// Generate a weekly reflection based on memory and current plan
@@ -110,7 +121,7 @@ impl ReflectionGenerator {
 
         // Generate summary text
         let summary = if self.config.use_llm {
-            self.generate_llm_summary(&changes, &adaptations, &suggestions)
+            self.generate_llm_summary(&changes, &adaptations, &suggestions, &metrics)
         } else {
             self.generate_rule_based_summary(&changes, &adaptations, &suggestions, &metrics)
         };
@@ -312,13 +323,50 @@ impl ReflectionGenerator {
 
     fn generate_llm_summary(
         &self,
-        _changes: &[String],
-        _adaptations: &[String],
-        _suggestions: &[String],
+        changes: &[String],
+        adaptations: &[String],
+        suggestions: &[String],
+        metrics: &WeeklyMetrics,
     ) -> String {
-        // Placeholder for LLM integration
-        // In production, this would call an LLM API
-        "Weekly reflection generated by AI assistant. (LLM integration pending)".to_string()
+        let fallback = || self.generate_rule_based_summary(changes, adaptations, suggestions, metrics);
+        let provider = match &self.llm {
+            Some(p) => p,
+            None => return fallback(),
+        };
+
+        let list = |items: &[String]| {
+            if items.is_empty() {
+                "- (none)".to_string()
+            } else {
+                items.iter().map(|i| format!("- {}", i)).collect::<Vec<_>>().join("\n")
+            }
+        };
+        let prompt = format!(
+            "Progress: {:.0}% of the roadmap, {} step(s) completed this week.\n\n\
+             What changed:\n{}\n\nWhy the plan adapted:\n{}\n\nSuggested next steps:\n{}",
+            metrics.progress_percentage,
+            metrics.steps_completed,
+            list(changes),
+            list(adaptations),
+            list(suggestions)
+        );
+        let messages = [
+            ChatMessage::system(
+                "You are a career coach writing a short weekly reflection for the user. \
+                 Write 2-4 encouraging, concrete sentences in the second person. \
+                 Only use the facts provided.",
+            ),
+            ChatMessage::user(&prompt),
+        ];
+
+        match provider.complete(&messages) {
+            Ok(summary) if !summary.is_empty() => summary,
+            Ok(_) => fallback(),
+            Err(e) => {
+                log::warn!("LLM reflection via {} failed, using rule-based summary: {}", provider.name(), e);
+                fallback()
+            }
+        }
     }
 }
 
@@ -398,6 +446,7 @@ pub fn generate_and_store_reflection(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::llm::MockProvider;
     use crate::agent::planner::{CareerPlanner, PlannerConfig, PlannerInput};
     use crate::agent::types::{CareerGoal, CareerRule, ResumeData};
     use std::collections::HashSet;
@@ -434,4 +483,34 @@ mod tests {
         assert!(!reflection.summary.is_empty());
         assert_eq!(reflection.user_id, "test_user");
     }
+
+    #[test]
+    fn test_reflection_with_mock_llm() {
+        let mock = Arc::new(MockProvider::new("You finished Python basics. Nice work!"));
+        let generator = ReflectionGenerator::new(ReflectionConfig::default()).with_llm(mock.clone());
+
+        let mut memory = CareerMemory::new("test_user");
+        memory.add_event(MemoryEvent::new(
+            "test_user",
+            MemoryEventType::StepCompleted,
+            "Completed: Learn Python basics",
+        ));
+        let roadmap = CareerPlanner::new(PlannerConfig::default()).generate_roadmap(&PlannerInput {
+            resume: ResumeData {
+                user_id: "test_user".to_string(),
+                ..Default::default()
+            },
+            assessments: Vec::new(),
+            goal: CareerGoal::new("test_user", "Test Goal"),
+            available_rules: vec![CareerRule::new("test", "Test Step", "technical_skill")],
+            deferred_steps: HashSet::new(),
+        });
+
+        let reflection = generator.generate_weekly_reflection(&memory, &roadmap);
+
+        assert_eq!(reflection.summary, "You finished Python basics. Nice work!");
+        let prompts = mock.prompts();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0][1].content.contains("Learn Python basics"));
+    }
 }
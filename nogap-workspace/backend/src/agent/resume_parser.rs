@@ -1,7 +1,8 @@
 //! Resume Parser Integration
 //!
-//! Calls the external Python resume parser and deserializes the output.
-//! Python is used ONLY for parsing - all intelligence remains in Rust.
+//! Parses resumes natively (see `native_parser`) and calls the external Python
+//! stages (evidence mapping, bottleneck analysis, strategy, agent loop),
+//! deserializing their output.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -9,7 +10,7 @@ use std::path::Path;
 use std::process::Command;
 use thiserror::Error;
 
-/// Output from the Python resume parser
+/// Structured resume data produced by the parsing stage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedResume {
     pub skills: Vec<String>,
@@ -24,6 +25,12 @@ pub struct ParsedExperience {
     pub company: String,
     #[serde(default)]
     pub role: Option<String>,
+    /// "YYYY" or "YYYY-MM"
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// "YYYY", "YYYY-MM" or "present"
+    #[serde(default)]
+    pub end_date: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +39,8 @@ pub struct ParsedEducation {
     pub degree: Option<String>,
     #[serde(default)]
     pub institution: Option<String>,
+    #[serde(default)]
+    pub year: Option<u32>,
 }
 
 // =============================================================================
@@ -177,6 +186,9 @@ pub enum ResumeParseError {
 
     #[error("Resume file not found: {0}")]
     FileNotFound(String),
+
+    #[error("Invalid resume upload: {0}")]
+    InvalidUpload(String),
 }
 
 /// Configuration for the resume parser
@@ -233,11 +245,10 @@ impl ResumeParserConfig {
     }
 }
 
-/// Parse a resume file using the external Python parser
+/// Parse a resume file natively (no Python required)
 ///
 /// # Arguments
-/// * `resume_path` - Path to the PDF or DOCX resume file
-/// * `config` - Parser configuration (Python path, script path)
+/// * `resume_path` - Path to the PDF, DOCX or TXT resume file
 ///
 /// # Returns
 /// * `Ok(ParsedResume)` - Parsed resume data
@@ -245,102 +256,27 @@ impl ResumeParserConfig {
 ///
 /// # Example
 /// ```ignore
-/// let config = ResumeParserConfig::default();
-/// let result = parse_resume("uploads/resume.pdf", &config)?;
+/// let result = parse_resume("uploads/resume.pdf")?;
 /// println!("Skills: {:?}", result.skills);
 /// ```
-pub fn parse_resume(
-    resume_path: &str,
-    config: &ResumeParserConfig,
-) -> Result<ParsedResume, ResumeParseError> {
+pub fn parse_resume(resume_path: &str) -> Result<ParsedResume, ResumeParseError> {
     // Validate resume file exists
     if !Path::new(resume_path).exists() {
         eprintln!("[parse_resume] ERROR: Resume file not found: {}", resume_path);
         return Err(ResumeParseError::FileNotFound(resume_path.to_string()));
     }
 
-    // Resolve and validate Python executable path
-    let python_exe = ResumeParserConfig::resolve_path(&config.python_path)?;
-    if !python_exe.exists() {
-        eprintln!("[parse_resume] ERROR: Python executable not found: {}", python_exe.display());
-        return Err(ResumeParseError::ScriptNotFound(format!("Python not found: {}", python_exe.display())));
-    }
-
-    // Resolve and validate script path
-    let script_path = ResumeParserConfig::resolve_path(&config.script_path)?;
-    if !script_path.exists() {
-        eprintln!("[parse_resume] ERROR: Script not found: {}", script_path.display());
-        return Err(ResumeParseError::ScriptNotFound(format!("Script not found: {}", script_path.display())));
-    }
-
     // ===== PIPELINE STAGE: parse_resume =====
     eprintln!("[pipeline:parse_resume] ========== STAGE START ==========");
-    eprintln!("[pipeline:parse_resume] Resolved paths:");
-    eprintln!("[pipeline:parse_resume]   Python: {}", python_exe.display());
-    eprintln!("[pipeline:parse_resume]   Script: {}", script_path.display());
     eprintln!("[pipeline:parse_resume]   Resume: {}", resume_path);
-    eprintln!("[pipeline:parse_resume] Command: {:?} {:?} {:?}", python_exe, script_path, resume_path);
 
-    // Execute Python script with resolved paths
-    let output = Command::new(&python_exe)
-        .arg(&script_path)
-        .arg(resume_path)
-        .output()
-        .map_err(|e| {
-            eprintln!("[pipeline:parse_resume] ❌ FAILED to spawn process: {}", e);
-            ResumeParseError::ExecutionFailed(format!("Stage: parse_resume | Error: {}", e))
-        })?;
-
-    // Get stdout and stderr
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let exit_code = output.status.code();
-    
-    eprintln!("[pipeline:parse_resume] Exit code: {:?}", exit_code);
-    eprintln!("[pipeline:parse_resume] stdout length: {} bytes", stdout.len());
-    eprintln!("[pipeline:parse_resume] stderr length: {} bytes", stderr.len());
-    
-    if !stderr.is_empty() {
-        eprintln!("[pipeline:parse_resume] stderr output:");
-        eprintln!("--- stderr start ---");
-        eprintln!("{}", stderr);
-        eprintln!("--- stderr end ---");
-    }
-    
-    if stdout.is_empty() {
-        eprintln!("[pipeline:parse_resume] ❌ ERROR: stdout is empty");
-        eprintln!("[pipeline:parse_resume] This indicates Python script did not produce output");
-    }
-    
-    if exit_code != Some(0) {
-        eprintln!("[pipeline:parse_resume] ❌ Non-zero exit code: {:?}", exit_code);
-        return Err(ResumeParseError::ExecutionFailed(format!(
-            "Stage: parse_resume | Exit code: {:?} | stderr: {}",
-            exit_code, stderr
-        )));
-    }
-
-    // Check if it's an error response
-    if let Ok(error) = serde_json::from_str::<ParserError>(&stdout) {
-        eprintln!("[pipeline:parse_resume] ❌ Parser returned error: {}", error.error);
-        return Err(ResumeParseError::ParserError(format!("Stage: parse_resume | {}", error.error)));
-    }
-
-    // Parse successful response
-    let parsed: ParsedResume = serde_json::from_str(&stdout)
-        .map_err(|e| {
-            let preview = &stdout[..stdout.len().min(500)];
-            eprintln!("[pipeline:parse_resume] ❌ JSON parse failed: {}", e);
-            eprintln!("[pipeline:parse_resume] stdout preview:");
-            eprintln!("--- stdout preview (first 500 chars) ---");
-            eprintln!("{}", preview);
-            eprintln!("--- end preview ---");
-            ResumeParseError::InvalidJson(format!(
-                "Stage: parse_resume | JSON error: {} | stdout length: {} bytes | preview: {}",
-                e, stdout.len(), preview
-            ))
-        })?;
+    let parsed = super::native_parser::parse_resume_file(Path::new(resume_path)).map_err(|e| {
+        eprintln!("[pipeline:parse_resume] ❌ FAILED: {}", e);
+        e
+    })?;
 
+    eprintln!("[pipeline:parse_resume] Skills found: {}", parsed.skills.len());
+    eprintln!("[pipeline:parse_resume] Experience entries: {}", parsed.experience.len());
     eprintln!("[pipeline:parse_resume] ✅ SUCCESS");
     eprintln!("[pipeline:parse_resume] ========== STAGE END ==========");
     Ok(parsed)
@@ -459,7 +395,7 @@ pub fn map_evidence(
 /// Parse resume and map evidence in one call (full pipeline)
 ///
 /// # Arguments
-/// * `resume_path` - Path to the PDF or DOCX resume file
+/// * `resume_path` - Path to the PDF, DOCX or TXT resume file
 /// * `config` - Parser configuration
 ///
 /// # Returns
@@ -468,7 +404,7 @@ pub fn parse_and_map(
     resume_path: &str,
     config: &ResumeParserConfig,
 ) -> Result<(ParsedResume, EvidenceMap), ResumeParseError> {
-    let parsed = parse_resume(resume_path, config)?;
+    let parsed = parse_resume(resume_path)?;
     let evidence = map_evidence(&parsed, config)?;
     Ok((parsed, evidence))
 }
@@ -700,7 +636,7 @@ pub fn select_strategy(
 /// Full pipeline: parse, map evidence, analyze bottlenecks, select strategy
 ///
 /// # Arguments
-/// * `resume_path` - Path to the PDF or DOCX resume file
+/// * `resume_path` - Path to the PDF, DOCX or TXT resume file
 /// * `config` - Parser configuration
 ///
 /// # Returns
@@ -709,7 +645,22 @@ pub fn full_pipeline(
     resume_path: &str,
     config: &ResumeParserConfig,
 ) -> Result<(ParsedResume, EvidenceMap, BottleneckAnalysis, StrategySelection), ResumeParseError> {
-    let parsed = parse_resume(resume_path, config)?;
+    analyze_parsed(parse_resume(resume_path)?, config)
+}
+
+/// Map evidence, analyze bottlenecks and select strategy for a parsed resume
+///
+/// Parsing is native, but these three stages still run the Python scripts
+/// next to `config.script_path`; porting them is out of scope for the
+/// native parser.
+///
+/// # Arguments
+/// * `parsed` - Output of `native_parser::parse_resume_text` or `parse_resume`
+/// * `config` - Parser configuration
+pub fn analyze_parsed(
+    parsed: ParsedResume,
+    config: &ResumeParserConfig,
+) -> Result<(ParsedResume, EvidenceMap, BottleneckAnalysis, StrategySelection), ResumeParseError> {
     let evidence = map_evidence(&parsed, config)?;
     let analysis = analyze_bottlenecks(&evidence, config)?;
     let strategy = select_strategy(&analysis, &evidence, config)?;
@@ -886,7 +837,7 @@ impl ParsedResume {
     pub fn to_resume_data(&self, user_id: &str) -> super::types::ResumeData {
         super::types::ResumeData {
             user_id: user_id.to_string(),
-            name: None, // Not extracted by pyresparser
+            name: None, // Not extracted by the parser
             email: None,
             current_role: self
                 .experience
@@ -902,7 +853,7 @@ impl ParsedResume {
                         institution: e.institution.clone().unwrap_or_default(),
                         degree: e.degree.clone().unwrap_or_default(),
                         field: None,
-                        year: None,
+                        year: e.year,
                     })
                 })
                 .collect(),
@@ -912,7 +863,11 @@ impl ParsedResume {
                 .map(|e| super::types::ExperienceEntry {
                     company: e.company.clone(),
                     role: e.role.clone().unwrap_or_default(),
-                    duration: None,
+                    duration: match (&e.start_date, &e.end_date) {
+                        (Some(start), Some(end)) => Some(format!("{} - {}", start, end)),
+                        (Some(start), None) => Some(start.clone()),
+                        _ => None,
+                    },
                     description: None,
                 })
                 .collect(),
@@ -955,10 +910,13 @@ mod tests {
             experience: vec![ParsedExperience {
                 company: "Acme".to_string(),
                 role: Some("Dev".to_string()),
+                start_date: Some("2020-01".to_string()),
+                end_date: Some("present".to_string()),
             }],
             education: vec![ParsedEducation {
                 degree: Some("MS".to_string()),
                 institution: Some("Stanford".to_string()),
+                year: Some(2019),
             }],
             total_experience: Some(3.5),
             raw_text: "test".to_string(),
@@ -969,6 +927,8 @@ mod tests {
         assert_eq!(data.skills, vec!["Python"]);
         assert_eq!(data.years_experience, Some(3));
         assert_eq!(data.current_role, Some("Dev".to_string()));
+        assert_eq!(data.experience[0].duration.as_deref(), Some("2020-01 - present"));
+        assert_eq!(data.education[0].year, Some(2019));
     }
 
     #[test]
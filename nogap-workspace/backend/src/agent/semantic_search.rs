@@ -0,0 +1,486 @@
+//! Semantic Memory Search Module
+//!
+//! Embedding-based retrieval over the memory timeline, so users can find
+//! relevant past entries beyond exact keyword matches through
+//! `/api/memory/{user_id}/search`.
+//! Vectors are stored next to the timeline in SQLite and computed lazily
+//! for events that have not been indexed with the current model yet.
+
+use super::llm::{http_agent, LlmError, DEFAULT_OLLAMA_URL, DEFAULT_OPENAI_URL};
+use super::memory::{MemoryEvent, MemoryStore};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Dimensions of the built-in local embedding
+pub const LOCAL_EMBEDDING_DIM: usize = 512;
+
+/// Texts sent per request to a remote embedding API
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// Matches below this cosine similarity are dropped
+const MIN_SCORE: f32 = 0.1;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "i", "in", "is",
+    "it", "my", "of", "on", "or", "that", "the", "this", "to", "was", "were", "with", "you", "your",
+];
+
+// ============================================================
+// EMBEDDING PROVIDERS
+// ============================================================
+
+/// Turns text into vectors whose cosine similarity reflects relatedness
+pub trait EmbeddingProvider: Send + Sync {
+    /// Model identifier; stored with each vector so a model change re-indexes
+    fn model(&self) -> &str;
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, LlmError>;
+}
+
+/// Which embedding backend to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingProviderKind {
+    /// Built-in hashed n-gram embedding (offline, no model download)
+    Local,
+    OpenAi,
+    Ollama,
+}
+
+/// Embedding settings
+#[derive(Debug, Clone)]
+pub struct EmbeddingConfig {
+    pub provider: EmbeddingProviderKind,
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub timeout_secs: u64,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            provider: EmbeddingProviderKind::Local,
+            base_url: None,
+            api_key: None,
+            model: None,
+            timeout_secs: 60,
+        }
+    }
+}
+
+impl EmbeddingConfig {
+    /// Read `CAREER_AGENT_EMBEDDING_PROVIDER` (local/openai/ollama) and
+    /// `CAREER_AGENT_EMBEDDING_MODEL`. The base URL and API key default to the
+    /// LLM settings (`CAREER_AGENT_LLM_BASE_URL`, `CAREER_AGENT_LLM_API_KEY`).
+    pub fn from_env() -> Result<Self, LlmError> {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let provider = match var("CAREER_AGENT_EMBEDDING_PROVIDER").map(|p| p.trim().to_lowercase()) {
+            None => defaults.provider,
+            Some(p) => match p.as_str() {
+                "local" => EmbeddingProviderKind::Local,
+                "openai" => EmbeddingProviderKind::OpenAi,
+                "ollama" => EmbeddingProviderKind::Ollama,
+                _ => return Err(LlmError::Config(format!("unknown embedding provider '{}'", p))),
+            },
+        };
+
+        Ok(Self {
+            provider,
+            base_url: var("CAREER_AGENT_EMBEDDING_BASE_URL").or_else(|| var("CAREER_AGENT_LLM_BASE_URL")),
+            api_key: var("CAREER_AGENT_LLM_API_KEY").or_else(|| var("OPENAI_API_KEY")),
+            model: var("CAREER_AGENT_EMBEDDING_MODEL"),
+            timeout_secs: defaults.timeout_secs,
+        })
+    }
+}
+
+/// Build the configured embedding provider
+pub fn build_embedder(config: &EmbeddingConfig) -> Arc<dyn EmbeddingProvider> {
+    match config.provider {
+        EmbeddingProviderKind::Local => Arc::new(LocalEmbedder::new()),
+        EmbeddingProviderKind::OpenAi => Arc::new(RemoteEmbedder {
+            agent: http_agent(config.timeout_secs),
+            api: RemoteApi::OpenAi,
+            base_url: config.base_url.clone().unwrap_or_else(|| DEFAULT_OPENAI_URL.to_string()),
+            api_key: config.api_key.clone(),
+            model: config
+                .model
+                .clone()
+                .unwrap_or_else(|| "text-embedding-3-small".to_string()),
+        }),
+        EmbeddingProviderKind::Ollama => Arc::new(RemoteEmbedder {
+            agent: http_agent(config.timeout_secs),
+            api: RemoteApi::Ollama,
+            base_url: config.base_url.clone().unwrap_or_else(|| DEFAULT_OLLAMA_URL.to_string()),
+            api_key: None,
+            model: config.model.clone().unwrap_or_else(|| "nomic-embed-text".to_string()),
+        }),
+    }
+}
+
+/// Offline embedding: word and character-trigram features hashed into a
+/// fixed-size vector. Related word forms ("learn", "learning") share trigrams,
+/// so matches go beyond exact keywords without downloading a model.
+pub struct LocalEmbedder {
+    model: String,
+}
+
+impl LocalEmbedder {
+    pub fn new() -> Self {
+        Self {
+            model: format!("local-hash-{}", LOCAL_EMBEDDING_DIM),
+        }
+    }
+
+    fn embed_one(text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; LOCAL_EMBEDDING_DIM];
+        let lower = text.to_lowercase();
+        let words = lower
+            .split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+            .filter(|w| !w.is_empty() && !STOP_WORDS.contains(w));
+
+        for word in words {
+            add_feature(&mut vector, word, 1.0);
+
+            let padded: Vec<char> = format!("^{}$", word).chars().collect();
+            let trigrams = padded.len().saturating_sub(2);
+            for window in padded.windows(3) {
+                let trigram: String = window.iter().collect();
+                add_feature(&mut vector, &trigram, 1.0 / trigrams as f32);
+            }
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+}
+
+impl Default for LocalEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmbeddingProvider for LocalEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+        Ok(texts.iter().map(|t| Self::embed_one(t)).collect())
+    }
+}
+
+/// Hash a feature into the vector; one hash bit picks the sign to limit collisions
+fn add_feature(vector: &mut [f32], feature: &str, weight: f32) {
+    // FNV-1a: stable across runs and Rust versions, unlike `DefaultHasher`
+    let hash = feature
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    let index = (hash % vector.len() as u64) as usize;
+    let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+    vector[index] += sign * weight;
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+enum RemoteApi {
+    /// `POST {base}/embeddings`
+    OpenAi,
+    /// `POST {base}/api/embed`
+    Ollama,
+}
+
+/// Embeddings from an OpenAI-compatible API or a local Ollama server
+pub struct RemoteEmbedder {
+    agent: ureq::Agent,
+    api: RemoteApi,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+}
+
+impl EmbeddingProvider for RemoteEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+        let base = self.base_url.trim_end_matches('/');
+        let url = match self.api {
+            RemoteApi::OpenAi => format!("{}/embeddings", base),
+            RemoteApi::Ollama => format!("{}/api/embed", base),
+        };
+
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH_SIZE) {
+            let mut request = self.agent.post(&url);
+            if let Some(key) = &self.api_key {
+                request = request.set("Authorization", &format!("Bearer {}", key));
+            }
+            let response: Value = request
+                .send_json(json!({ "model": self.model, "input": batch }))
+                .map_err(|e| LlmError::Request(e.to_string()))?
+                .into_json()
+                .map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+            let batch_vectors = parse_embeddings(&response)?;
+            if batch_vectors.len() != batch.len() {
+                return Err(LlmError::InvalidResponse(format!(
+                    "expected {} embeddings, got {}",
+                    batch.len(),
+                    batch_vectors.len()
+                )));
+            }
+            vectors.extend(batch_vectors);
+        }
+        Ok(vectors)
+    }
+}
+
+/// Read vectors from either `{"data": [{"embedding": [...]}]}` (OpenAI)
+/// or `{"embeddings": [[...]]}` (Ollama)
+fn parse_embeddings(response: &Value) -> Result<Vec<Vec<f32>>, LlmError> {
+    let to_vector = |v: &Value| -> Option<Vec<f32>> {
+        v.as_array()?.iter().map(|x| x.as_f64().map(|f| f as f32)).collect()
+    };
+
+    let vectors: Option<Vec<Vec<f32>>> = if let Some(data) = response["data"].as_array() {
+        data.iter().map(|item| to_vector(&item["embedding"])).collect()
+    } else if let Some(embeddings) = response["embeddings"].as_array() {
+        embeddings.iter().map(to_vector).collect()
+    } else {
+        None
+    };
+
+    vectors.ok_or_else(|| LlmError::InvalidResponse(response.to_string()))
+}
+
+// ============================================================
+// SEMANTIC INDEX
+// ============================================================
+
+/// Why a memory search failed
+#[derive(Error, Debug)]
+pub enum SearchError {
+    /// The embedding provider failed (upstream)
+    #[error(transparent)]
+    Embedding(#[from] LlmError),
+
+    /// Reading or writing the local index failed
+    #[error("Memory storage error: {0}")]
+    Storage(#[from] rusqlite::Error),
+}
+
+/// A memory event ranked by similarity to a query
+#[derive(Debug, Clone, Serialize)]
+pub struct MemorySearchHit {
+    pub event: MemoryEvent,
+    /// Cosine similarity (higher is more relevant)
+    pub score: f32,
+}
+
+/// Embedding index over the memory timeline
+pub struct SemanticIndex {
+    conn: Arc<Mutex<Connection>>,
+    embedder: Arc<dyn EmbeddingProvider>,
+}
+
+impl SemanticIndex {
+    /// Create the index on the memory store's database
+    pub fn new(store: &MemoryStore, embedder: Arc<dyn EmbeddingProvider>) -> rusqlite::Result<Self> {
+        let conn = store.connection();
+        conn.lock().unwrap().execute(
+            "CREATE TABLE IF NOT EXISTS memory_embeddings (
+                event_id TEXT NOT NULL REFERENCES memory_events(id) ON DELETE CASCADE,
+                model TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                PRIMARY KEY (event_id, model)
+            )",
+            [],
+        )?;
+        Ok(Self { conn, embedder })
+    }
+
+    pub fn model(&self) -> &str {
+        self.embedder.model()
+    }
+
+    /// Find the user's memory events most similar to `query`
+    pub fn search(
+        &self,
+        store: &MemoryStore,
+        user_id: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<MemorySearchHit>, SearchError> {
+        let events = store.get_user_memory(user_id)?.timeline;
+        if events.is_empty() || query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let vectors = self.vectors_for(user_id, &events)?;
+        let query_vector = self
+            .embedder
+            .embed(&[query.to_string()])?
+            .pop()
+            .ok_or_else(|| LlmError::InvalidResponse("no query embedding".to_string()))?;
+
+        let mut hits: Vec<MemorySearchHit> = events
+            .into_iter()
+            .filter_map(|event| {
+                let score = cosine(&query_vector, vectors.get(&event.id)?);
+                (score >= MIN_SCORE).then_some(MemorySearchHit { event, score })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Stored vectors for the user's events, embedding and saving any that are missing
+    fn vectors_for(
+        &self,
+        user_id: &str,
+        events: &[MemoryEvent],
+    ) -> Result<HashMap<String, Vec<f32>>, SearchError> {
+        let model = self.embedder.model().to_string();
+        let mut vectors = HashMap::new();
+        {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare(
+                "SELECT e.event_id, e.vector FROM memory_embeddings e
+                 JOIN memory_events m ON m.id = e.event_id
+                 WHERE m.user_id = ?1 AND e.model = ?2",
+            )?;
+            let rows = stmt.query_map(params![user_id, model], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?;
+            for row in rows {
+                let (event_id, blob) = row?;
+                vectors.insert(event_id, decode_vector(&blob));
+            }
+        }
+
+        let missing: Vec<&MemoryEvent> = events.iter().filter(|e| !vectors.contains_key(&e.id)).collect();
+        if missing.is_empty() {
+            return Ok(vectors);
+        }
+
+        let texts: Vec<String> = missing.iter().map(|e| event_text(e)).collect();
+        let embedded = self.embedder.embed(&texts)?;
+
+        let conn = self.conn.lock().unwrap();
+        for (event, vector) in missing.into_iter().zip(embedded) {
+            conn.execute(
+                "INSERT OR REPLACE INTO memory_embeddings (event_id, model, vector) VALUES (?1, ?2, ?3)",
+                params![event.id, model, encode_vector(&vector)],
+            )?;
+            vectors.insert(event.id.clone(), vector);
+        }
+        Ok(vectors)
+    }
+}
+
+/// Text that represents an event in the index
+fn event_text(event: &MemoryEvent) -> String {
+    let mut text = format!(
+        "{} {}",
+        event.event_type.as_str().replace('_', " "),
+        event.description
+    );
+    if !event.tags.is_empty() {
+        text.push(' ');
+        text.push_str(&event.tags.join(" "));
+    }
+    text
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_vector(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::memory::MemoryEventType;
+
+    #[test]
+    fn test_semantic_search_ranks_related_events() {
+        let store = MemoryStore::in_memory().unwrap();
+        let index = SemanticIndex::new(&store, Arc::new(LocalEmbedder::new())).unwrap();
+
+        for (event_type, description) in [
+            (MemoryEventType::StepCompleted, "Completed: Learn Kubernetes deployments"),
+            (MemoryEventType::GoalSet, "Goal set: Become a data scientist"),
+            (MemoryEventType::StepSkipped, "Skipped: Public speaking workshop"),
+        ] {
+            store
+                .record_event(&MemoryEvent::new("test_user", event_type, description))
+                .unwrap();
+        }
+
+        let hits = index.search(&store, "test_user", "learning kubernetes", 5).unwrap();
+        assert!(!hits.is_empty());
+        assert!(hits[0].event.description.contains("Kubernetes"));
+
+        let hits = index.search(&store, "test_user", "data science", 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].event.description.contains("data scientist"));
+
+        // Vectors are persisted and only computed once per event
+        let stored: i64 = store
+            .connection()
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM memory_embeddings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, 3);
+
+        assert!(index.search(&store, "other_user", "kubernetes", 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_embeddings_and_vector_roundtrip() {
+        let openai = json!({ "data": [{ "embedding": [0.1, 0.2] }, { "embedding": [0.3, 0.4] }] });
+        assert_eq!(parse_embeddings(&openai).unwrap().len(), 2);
+
+        let ollama = json!({ "embeddings": [[0.5, -0.5]] });
+        assert_eq!(parse_embeddings(&ollama).unwrap(), vec![vec![0.5, -0.5]]);
+        assert!(parse_embeddings(&json!({ "error": "no model" })).is_err());
+
+        let vector = vec![0.25f32, -1.5, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+}
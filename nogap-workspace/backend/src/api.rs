@@ -1,16 +1,28 @@
 //! Web API Module
 //!
 //! Exposes RESTful endpoints for the Career Assistant frontend.
-//! All endpoints return JSON and require no authentication (prototype mode).
+//! All endpoints return JSON. Per-user endpoints accept an
+//! `Authorization: Bearer <token>` header from `/api/auth/login`; without one
+//! the request's `user_id` is trusted unless `CAREER_AGENT_REQUIRE_AUTH` is set.
 
 use crate::agent::{
-    memory::{self, MemoryStore, MemoryEvent, MemoryEventType},
+    gap_analysis::{self, GapReportStore},
+    llm::{self, LlmConfig, LlmProvider},
+    memory::{self, MemoryEvent, MemoryEventType, MemoryQuery, MemoryQueryError, MemoryStore},
+    native_parser::{self, MAX_RESUME_BYTES},
+    plan_versions::{self, PlanVersionStore, PlanVersionSummary},
     planner::{self, CareerPlanner, CareerRoadmap, PlannerConfig, PlannerInput, RoadmapEdit},
     reflection::{ReflectionGenerator, ReflectionConfig, ReflectionStore},
+    resume_parser::ResumeParseError,
+    semantic_search::{self, EmbeddingConfig, SearchError, SemanticIndex},
     types::{CareerGoal, CareerRule, ResumeData},
 };
+use crate::auth::{self, AuthConfig, AuthError, UserStore};
+use crate::calendar::{self, CalendarFeed, CalendarFeedStore, CalendarOptions};
+use crate::events::{AgentEvent, AgentEventKind, EventBus};
+use crate::scheduler::{self, DigestConfig, DigestError, DigestStore, Mailer, SchedulerConfig};
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{http::header, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashSet;
@@ -23,25 +35,120 @@ use std::sync::{Arc, Mutex};
 /// Shared application state
 pub struct AppState {
     pub memory_store: MemoryStore,
+    /// Embedding index over the memory timeline
+    pub semantic_index: SemanticIndex,
+    pub user_store: UserStore,
     pub reflection_store: ReflectionStore,
+    /// Every saved version of each user's roadmap
+    pub plan_versions: PlanVersionStore,
+    /// Email digest preferences
+    pub digest_store: DigestStore,
+    /// Read-only calendar subscription tokens
+    pub calendar_feeds: CalendarFeedStore,
+    /// Digest delivery; `None` when `CAREER_AGENT_SENDMAIL` is unset
+    pub mailer: Option<Arc<dyn Mailer>>,
+    /// Live agent activity for `/api/events`
+    pub events: EventBus,
     pub roadmaps: Mutex<std::collections::HashMap<String, CareerRoadmap>>,
+    /// Per-user lock held while a roadmap change is saved and applied, so the
+    /// live roadmap always matches the latest saved version
+    pub roadmap_locks: Mutex<std::collections::HashMap<String, Arc<Mutex<()>>>>,
     pub resumes: Mutex<std::collections::HashMap<String, ResumeData>>,
     pub goals: Mutex<std::collections::HashMap<String, CareerGoal>>,
+    /// Latest job description gap report per user
+    pub gap_reports: GapReportStore,
     /// Default career rules (skills/milestones)
     pub career_rules: Vec<CareerRule>,
+    /// Text generation backend; `None` means rule-based text only
+    pub llm: Option<Arc<dyn LlmProvider>>,
 }
 
 impl AppState {
+    /// State backed by the on-disk memory database (see `CAREER_AGENT_DB`),
+    /// configured from the environment
     pub fn new() -> Result<Self, rusqlite::Error> {
+        let embedding_config = EmbeddingConfig::from_env().unwrap_or_else(|e| {
+            log::warn!("{}; using the local embedding", e);
+            EmbeddingConfig::default()
+        });
+        let llm = LlmConfig::from_env()
+            .and_then(|config| llm::build_provider(&config))
+            .unwrap_or_else(|e| {
+                log::warn!("{}; falling back to rule-based text", e);
+                None
+            });
+
+        Self::with_memory_store(
+            MemoryStore::from_env()?,
+            AuthConfig::from_env(),
+            &embedding_config,
+            llm,
+            scheduler::build_mailer(&DigestConfig::from_env()),
+        )
+    }
+
+    /// State backed by a throwaway in-memory database, with default settings
+    /// and no LLM or mailer, regardless of the environment
+    pub fn in_memory() -> Result<Self, rusqlite::Error> {
+        Self::with_memory_store(
+            MemoryStore::in_memory()?,
+            AuthConfig::default(),
+            &EmbeddingConfig::default(),
+            None,
+            None,
+        )
+    }
+
+    fn with_memory_store(
+        memory_store: MemoryStore,
+        auth_config: AuthConfig,
+        embedding_config: &EmbeddingConfig,
+        llm: Option<Arc<dyn LlmProvider>>,
+        mailer: Option<Arc<dyn Mailer>>,
+    ) -> Result<Self, rusqlite::Error> {
+        let user_store = UserStore::new(memory_store.connection(), auth_config)?;
+        let digest_store = DigestStore::new(memory_store.connection())?;
+        let plan_versions = PlanVersionStore::new(memory_store.connection())?;
+        let gap_reports = GapReportStore::new(memory_store.connection())?;
+        let calendar_feeds = CalendarFeedStore::new(memory_store.connection())?;
+        // Restore each user's latest roadmap from its version history
+        let roadmaps = plan_versions
+            .latest_roadmaps()?
+            .into_iter()
+            .map(|r| (r.user_id.clone(), r))
+            .collect();
+        let semantic_index =
+            SemanticIndex::new(&memory_store, semantic_search::build_embedder(embedding_config))?;
+
         Ok(Self {
-            memory_store: MemoryStore::in_memory()?,
+            memory_store,
+            semantic_index,
+            user_store,
             reflection_store: ReflectionStore::new(),
-            roadmaps: Mutex::new(std::collections::HashMap::new()),
+            plan_versions,
+            digest_store,
+            calendar_feeds,
+            mailer,
+            events: EventBus::new(),
+            roadmaps: Mutex::new(roadmaps),
+            roadmap_locks: Mutex::new(std::collections::HashMap::new()),
             resumes: Mutex::new(std::collections::HashMap::new()),
             goals: Mutex::new(std::collections::HashMap::new()),
+            gap_reports,
             career_rules: default_career_rules(),
+            llm,
         })
     }
+
+    /// The lock that serializes roadmap changes for one user
+    pub fn roadmap_lock(&self, user_id: &str) -> Arc<Mutex<()>> {
+        self.roadmap_locks
+            .lock()
+            .unwrap()
+            .entry(user_id.to_string())
+            .or_default()
+            .clone()
+    }
 }
 
 /// Default career rules for demo
@@ -164,6 +271,13 @@ pub struct UploadResumeRequest {
     pub raw_text: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct UploadResumeFileQuery {
+    pub user_id: String,
+    /// Original filename, used to detect the format when no content type is sent
+    pub filename: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct SetGoalRequest {
     pub user_id: String,
@@ -172,6 +286,13 @@ pub struct SetGoalRequest {
     pub timeline_months: Option<u32>,
 }
 
+#[derive(Deserialize)]
+pub struct GapAnalysisRequest {
+    pub user_id: String,
+    pub job_title: Option<String>,
+    pub job_description: String,
+}
+
 #[derive(Deserialize)]
 pub struct GenerateRoadmapRequest {
     pub user_id: String,
@@ -184,6 +305,73 @@ pub struct EditRoadmapRequest {
     pub edit: RoadmapEdit,
 }
 
+#[derive(Deserialize)]
+pub struct AddStepRequest {
+    pub title: String,
+    pub description: Option<String>,
+    /// "learn", "practice", "apply", "certify" or "network" (defaults to "learn")
+    pub step_type: Option<String>,
+    /// Insert after this step; appended when omitted
+    pub after_step_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderStepsRequest {
+    /// Every step ID in the new order
+    pub step_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PlanDiffQuery {
+    /// Defaults to the version before `to`
+    pub from: Option<u32>,
+    /// Defaults to the latest version
+    pub to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct DigestSubscriptionRequest {
+    pub email: String,
+    /// Defaults to true
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct MemorySearchQuery {
+    pub q: String,
+    /// Defaults to 10, capped at 50
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct EventStreamQuery {
+    /// Session token; `EventSource` cannot send an `Authorization` header
+    pub token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CalendarQuery {
+    /// Weekday for learning blocks, e.g. "sat" or "monday" (default Saturday)
+    pub day: Option<String>,
+    /// Local start hour of learning blocks (default 10)
+    pub hour: Option<u32>,
+    /// Length of learning blocks in minutes (default 90)
+    pub minutes: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct AuthRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct AuthResponse {
+    pub user: auth::User,
+    pub token: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Deserialize)]
 pub struct OutcomeRequest {
     pub session: serde_json::Value,
@@ -224,6 +412,115 @@ impl<T: Serialize> ApiResponse<T> {
     }
 }
 
+// ============================================================
+// AUTHENTICATION
+// ============================================================
+
+/// Map an authentication failure to an HTTP response
+fn auth_error_response(err: &AuthError) -> HttpResponse {
+    let body = ApiResponse::<()>::error(&err.to_string());
+    match err {
+        AuthError::Unauthorized | AuthError::InvalidCredentials => {
+            HttpResponse::Unauthorized().json(body)
+        }
+        AuthError::Forbidden => HttpResponse::Forbidden().json(body),
+        AuthError::UsernameTaken => HttpResponse::Conflict().json(body),
+        AuthError::Invalid(_) => HttpResponse::BadRequest().json(body),
+        AuthError::Hashing(_) | AuthError::Database(_) => {
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+/// Resolve the user a request acts as (see `UserStore::resolve_user`)
+fn authorize(
+    data: &AppState,
+    http: &HttpRequest,
+    claimed_user_id: Option<&str>,
+) -> Result<String, Box<HttpResponse>> {
+    let header_value = http
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    data.user_store
+        .resolve_user(auth::bearer_token(header_value), claimed_user_id)
+        .map_err(|e| Box::new(auth_error_response(&e)))
+}
+
+/// Register a new user
+async fn register(
+    data: web::Data<Arc<AppState>>,
+    req: web::Json<AuthRequest>,
+) -> impl Responder {
+    // Argon2 is deliberately slow, so hash off the async workers
+    let state = data.get_ref().clone();
+    let req = req.into_inner();
+    match web::block(move || state.user_store.register(&req.username, &req.password)).await {
+        Ok(Ok((user, session))) => HttpResponse::Ok().json(ApiResponse::success(AuthResponse {
+            user,
+            token: session.token,
+            expires_at: session.expires_at,
+        })),
+        Ok(Err(e)) => auth_error_response(&e),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Registration failed: {}", e))),
+    }
+}
+
+/// Log in and receive a bearer token
+async fn login(
+    data: web::Data<Arc<AppState>>,
+    req: web::Json<AuthRequest>,
+) -> impl Responder {
+    let state = data.get_ref().clone();
+    let req = req.into_inner();
+    match web::block(move || state.user_store.login(&req.username, &req.password)).await {
+        Ok(Ok((user, session))) => HttpResponse::Ok().json(ApiResponse::success(AuthResponse {
+            user,
+            token: session.token,
+            expires_at: session.expires_at,
+        })),
+        Ok(Err(e)) => auth_error_response(&e),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Login failed: {}", e))),
+    }
+}
+
+/// End the current session
+async fn logout(data: web::Data<Arc<AppState>>, http: HttpRequest) -> impl Responder {
+    let header_value = http
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    match auth::bearer_token(header_value) {
+        Some(token) => match data.user_store.logout(token) {
+            Ok(()) => HttpResponse::Ok().json(ApiResponse::success("Logged out")),
+            Err(e) => auth_error_response(&e),
+        },
+        None => auth_error_response(&AuthError::Unauthorized),
+    }
+}
+
+/// Get the currently authenticated user
+async fn current_user(data: web::Data<Arc<AppState>>, http: HttpRequest) -> impl Responder {
+    let header_value = http
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    let token = match auth::bearer_token(header_value) {
+        Some(t) => t,
+        None => return auth_error_response(&AuthError::Unauthorized),
+    };
+
+    match data.user_store.authenticate(token) {
+        Ok(user) => HttpResponse::Ok().json(ApiResponse::success(user)),
+        Err(e) => auth_error_response(&e),
+    }
+}
+
 // ============================================================
 // API HANDLERS
 // ============================================================
@@ -240,10 +537,16 @@ async fn health_check() -> impl Responder {
 /// Upload/update resume (SENSE phase)
 async fn upload_resume(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<UploadResumeRequest>,
 ) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&req.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
     let resume = ResumeData {
-        user_id: req.user_id.clone(),
+        user_id: user_id.clone(),
         name: req.name.clone(),
         email: None,
         current_role: req.current_role.clone(),
@@ -253,37 +556,115 @@ async fn upload_resume(
         experience: vec![],
         raw_text: req.raw_text.clone().unwrap_or_default(),
     };
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::SensingStarted, "Reading resume"));
 
     // Store resume
     {
         let mut resumes = data.resumes.lock().unwrap();
-        resumes.insert(req.user_id.clone(), resume.clone());
+        resumes.insert(user_id.clone(), resume.clone());
     }
 
     // Record in memory
-    let _ = memory::record_resume_upload(&data.memory_store, &req.user_id, "resume_data");
+    let _ = memory::record_resume_upload(&data.memory_store, &user_id, "resume_data");
+    data.events.publish(
+        AgentEvent::new(&user_id, AgentEventKind::SensingComplete, "Resume updated")
+            .with_data(json!({ "skills": resume.skills.len() })),
+    );
 
     HttpResponse::Ok().json(ApiResponse::success(resume))
 }
 
+/// Upload a resume document (PDF/DOCX/TXT) as the raw request body (SENSE phase)
+async fn upload_resume_file(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    query: web::Query<UploadResumeFileQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&query.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let content_type = http
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let format = match native_parser::validate_upload(&body, query.filename.as_deref(), content_type) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e.to_string())),
+    };
+
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::SensingStarted, "Parsing resume file"));
+    // Parsing is CPU-bound and pdf-extract can panic on malformed files, so run it
+    // on the blocking pool and turn a panic into a parse error
+    let parse = web::block(move || {
+        std::panic::catch_unwind(|| native_parser::parse_resume_bytes(&body, format)).unwrap_or_else(|_| {
+            Err(ResumeParseError::ParserError(
+                "the document is malformed and could not be read".to_string(),
+            ))
+        })
+    })
+    .await;
+    let parsed = match parse {
+        Ok(Ok(p)) => p,
+        Ok(Err(e)) => {
+            data.events.publish(AgentEvent::new(&user_id, AgentEventKind::Failed, &e.to_string()));
+            return HttpResponse::UnprocessableEntity()
+                .json(ApiResponse::<()>::error(&e.to_string()));
+        }
+        Err(e) => {
+            data.events.publish(AgentEvent::new(&user_id, AgentEventKind::Failed, &e.to_string()));
+            return HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::error(&format!("Resume parsing failed: {}", e)));
+        }
+    };
+    let resume = parsed.to_resume_data(&user_id);
+
+    // Store resume
+    {
+        let mut resumes = data.resumes.lock().unwrap();
+        resumes.insert(user_id.clone(), resume.clone());
+    }
+
+    // Record in memory
+    let filename = query.filename.as_deref().unwrap_or("resume_upload");
+    let _ = memory::record_resume_upload(&data.memory_store, &user_id, filename);
+    data.events.publish(
+        AgentEvent::new(&user_id, AgentEventKind::SensingComplete, "Resume parsed")
+            .with_data(json!({ "skills": resume.skills.len(), "experience": parsed.experience.len() })),
+    );
+
+    HttpResponse::Ok().json(ApiResponse::success(json!({
+        "resume": resume,
+        "parsed": parsed,
+    })))
+}
+
 /// Set career goal
 async fn set_goal(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<SetGoalRequest>,
 ) -> impl Responder {
-    let mut goal = CareerGoal::new(&req.user_id, &req.title);
+    let user_id = match authorize(&data, &http, Some(&req.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let mut goal = CareerGoal::new(&user_id, &req.title);
     goal.target_role = req.target_role.clone();
     goal.timeline_months = req.timeline_months;
 
     // Store goal
     {
         let mut goals = data.goals.lock().unwrap();
-        goals.insert(req.user_id.clone(), goal.clone());
+        goals.insert(user_id.clone(), goal.clone());
     }
 
     // Record in memory
     let event = MemoryEvent::new(
-        &req.user_id,
+        &user_id,
         MemoryEventType::GoalSet,
         &format!("Set career goal: {}", req.title),
     );
@@ -295,12 +676,20 @@ async fn set_goal(
 /// Generate career roadmap (PLAN phase) - STATE-GATED
 async fn generate_roadmap(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<GenerateRoadmapRequest>,
 ) -> impl Responder {
     use crate::agent::resume_parser::ResumeParserConfig;
     use std::process::{Command, Stdio};
     use std::io::Write;
 
+    let user_id = match authorize(&data, &http, Some(&req.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::PlanningStarted, "Generating roadmap"));
+
     // Check if session is provided for state-gating
     if let Some(session_json) = &req.session {
         // Call Python roadmap_generator.py with session
@@ -308,15 +697,20 @@ async fn generate_roadmap(
         let script_path = std::path::Path::new("resume_parser/roadmap_generator.py");
         
         if !script_path.exists() {
-            return HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("Roadmap generator script not found"));
+            return failure_response(
+                &data,
+                &user_id,
+                HttpResponse::InternalServerError(),
+                "Roadmap generator script not found",
+            );
         }
 
         // Prepare input JSON
         let input = json!({
             "session": session_json,
             "enhanced_snapshot": {},  // TODO: Get from session if available
-            "profile_signals": {}     // TODO: Get from session if available
+            "profile_signals": {},    // TODO: Get from session if available
+            "job_gap_goals": data.gap_reports.suggested_goals(&user_id),
         });
 
         // Execute Python script
@@ -329,16 +723,24 @@ async fn generate_roadmap(
         {
             Ok(c) => c,
             Err(e) => {
-                return HttpResponse::InternalServerError()
-                    .json(ApiResponse::<()>::error(&format!("Failed to execute Python: {}", e)));
+                return failure_response(
+                    &data,
+                    &user_id,
+                    HttpResponse::InternalServerError(),
+                    &format!("Failed to execute Python: {}", e),
+                );
             }
         };
 
         // Write input to stdin
         if let Some(mut stdin) = child.stdin.take() {
             if let Err(e) = stdin.write_all(input.to_string().as_bytes()) {
-                return HttpResponse::InternalServerError()
-                    .json(ApiResponse::<()>::error(&format!("Failed to write to Python stdin: {}", e)));
+                return failure_response(
+                    &data,
+                    &user_id,
+                    HttpResponse::InternalServerError(),
+                    &format!("Failed to write to Python stdin: {}", e),
+                );
             }
         }
 
@@ -346,8 +748,12 @@ async fn generate_roadmap(
         let output = match child.wait_with_output() {
             Ok(o) => o,
             Err(e) => {
-                return HttpResponse::InternalServerError()
-                    .json(ApiResponse::<()>::error(&format!("Failed to get Python output: {}", e)));
+                return failure_response(
+                    &data,
+                    &user_id,
+                    HttpResponse::InternalServerError(),
+                    &format!("Failed to get Python output: {}", e),
+                );
             }
         };
 
@@ -369,27 +775,34 @@ async fn generate_roadmap(
                 };
                 
                 // Log blocked attempt
-                println!("⚠️  Roadmap generation blocked for user {}: {}", req.user_id, error_msg);
-                
-                return HttpResponse::Forbidden()
-                    .json(ApiResponse::<()>::error(&error_msg));
+                println!("⚠️  Roadmap generation blocked for user {}: {}", user_id, error_msg);
+                return failure_response(&data, &user_id, HttpResponse::Forbidden(), &error_msg);
             }
             
-            return HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error(&format!("Roadmap generation failed: {}", stderr)));
+            return failure_response(
+                &data,
+                &user_id,
+                HttpResponse::InternalServerError(),
+                &format!("Roadmap generation failed: {}", stderr),
+            );
         }
 
         // Parse roadmap from stdout
         let roadmap_json: serde_json::Value = match serde_json::from_slice(&output.stdout) {
             Ok(j) => j,
             Err(e) => {
-                return HttpResponse::InternalServerError()
-                    .json(ApiResponse::<()>::error(&format!("Failed to parse roadmap JSON: {}", e)));
+                return failure_response(
+                    &data,
+                    &user_id,
+                    HttpResponse::InternalServerError(),
+                    &format!("Failed to parse roadmap JSON: {}", e),
+                );
             }
         };
 
         // Log successful generation
-        println!("✅ Roadmap generated for user {} (strategy in EXECUTE state)", req.user_id);
+        println!("✅ Roadmap generated for user {} (strategy in EXECUTE state)", user_id);
+        data.events.publish(AgentEvent::new(&user_id, AgentEventKind::PlanComplete, "Roadmap generated"));
 
         return HttpResponse::Ok().json(ApiResponse::success(roadmap_json));
     }
@@ -398,11 +811,15 @@ async fn generate_roadmap(
     // Get resume
     let resume = {
         let resumes = data.resumes.lock().unwrap();
-        match resumes.get(&req.user_id) {
+        match resumes.get(&user_id) {
             Some(r) => r.clone(),
             None => {
-                return HttpResponse::BadRequest()
-                    .json(ApiResponse::<()>::error("Resume not found. Please upload resume first."));
+                return failure_response(
+                    &data,
+                    &user_id,
+                    HttpResponse::BadRequest(),
+                    "Resume not found. Please upload resume first.",
+                );
             }
         }
     };
@@ -410,18 +827,26 @@ async fn generate_roadmap(
     // Get goal
     let goal = {
         let goals = data.goals.lock().unwrap();
-        match goals.get(&req.user_id) {
+        match goals.get(&user_id) {
             Some(g) => g.clone(),
-            None => CareerGoal::new(&req.user_id, "General Career Development"),
+            None => CareerGoal::new(&user_id, "General Career Development"),
         }
     };
 
+    // Career rules plus learning goals from the latest job gap analysis
+    let mut available_rules = data.career_rules.clone();
+    for rule in data.gap_reports.suggested_goals(&user_id) {
+        if !available_rules.iter().any(|r| r.id == rule.id) {
+            available_rules.push(rule);
+        }
+    }
+
     // Build planner input
     let input = PlannerInput {
         resume,
         assessments: vec![],
         goal,
-        available_rules: data.career_rules.clone(),
+        available_rules,
         deferred_steps: HashSet::new(),
     };
 
@@ -429,24 +854,154 @@ async fn generate_roadmap(
     let planner = CareerPlanner::new(PlannerConfig::default());
     let roadmap = planner.generate_roadmap(&input);
 
-    // Store roadmap
+    // Store roadmap and its version together, so concurrent edits cannot interleave
     {
-        let mut roadmaps = data.roadmaps.lock().unwrap();
-        roadmaps.insert(req.user_id.clone(), roadmap.clone());
+        let user_lock = data.roadmap_lock(&user_id);
+        let _guard = user_lock.lock().unwrap();
+        data.roadmaps.lock().unwrap().insert(user_id.clone(), roadmap.clone());
+        if let Err(e) = data.plan_versions.save(
+            &user_id,
+            plan_versions::AGENT_AUTHOR,
+            &format!("Generated roadmap with {} steps", roadmap.steps.len()),
+            None,
+            &roadmap,
+        ) {
+            log::error!("Failed to save roadmap version for {}: {}", user_id, e);
+        }
     }
 
     // Record in memory
-    let _ = memory::record_plan_generated(&data.memory_store, &req.user_id, roadmap.steps.len());
+    let _ = memory::record_plan_generated(&data.memory_store, &user_id, roadmap.steps.len());
+
+    for step in &roadmap.steps {
+        data.events.publish(
+            AgentEvent::new(
+                &user_id,
+                AgentEventKind::PlanStepGenerated,
+                &format!("Step {}: {}", step.order, step.title),
+            )
+            .with_data(json!(step)),
+        );
+    }
+    data.events.publish(
+        AgentEvent::new(
+            &user_id,
+            AgentEventKind::PlanComplete,
+            &format!("Roadmap generated with {} step(s)", roadmap.steps.len()),
+        )
+        .with_data(json!({ "roadmap_id": roadmap.id, "deferred": roadmap.deferred.len() })),
+    );
 
     HttpResponse::Ok().json(ApiResponse::success(roadmap))
 }
 
+/// Publish a `Failed` event so live clients leave the running phase, then build the error response
+fn failure_response(
+    data: &AppState,
+    user_id: &str,
+    mut response: actix_web::HttpResponseBuilder,
+    message: &str,
+) -> HttpResponse {
+    data.events.publish(AgentEvent::new(user_id, AgentEventKind::Failed, message));
+    response.json(ApiResponse::<()>::error(message))
+}
+
+/// Compare a pasted job description with the user's resume (SENSE phase)
+async fn analyze_job_gap(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    req: web::Json<GapAnalysisRequest>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&req.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    if req.job_description.trim().is_empty() {
+        return HttpResponse::BadRequest()
+            .json(ApiResponse::<()>::error("No job description provided"));
+    }
+
+    let resume = {
+        let resumes = data.resumes.lock().unwrap();
+        match resumes.get(&user_id) {
+            Some(r) => r.clone(),
+            None => {
+                return HttpResponse::BadRequest()
+                    .json(ApiResponse::<()>::error("Resume not found. Please upload resume first."));
+            }
+        }
+    };
+
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::SensingStarted, "Comparing job description with resume"));
+    let report = gap_analysis::analyze_gap(
+        &user_id,
+        req.job_title.as_deref(),
+        &req.job_description,
+        &resume,
+        &data.career_rules,
+    );
+
+    // Store report so the next roadmap generation picks up its goals
+    if let Err(e) = data.gap_reports.save(&report) {
+        return failure_response(
+            &data,
+            &user_id,
+            HttpResponse::InternalServerError(),
+            &format!("Database error: {}", e),
+        );
+    }
+
+    // Record in memory
+    let _ = memory::record_gap_analysis(
+        &data.memory_store,
+        &user_id,
+        report.job_title.as_deref(),
+        report.missing.len(),
+        report.match_score,
+    );
+    data.events.publish(
+        AgentEvent::new(
+            &user_id,
+            AgentEventKind::SensingComplete,
+            &format!("Job match {:.0}%, {} skill gap(s)", report.match_score * 100.0, report.missing.len()),
+        )
+        .with_data(json!({ "report_id": report.id })),
+    );
+
+    HttpResponse::Ok().json(ApiResponse::success(report))
+}
+
+/// Get the latest job gap report
+async fn get_job_gap(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.gap_reports.latest(&user_id) {
+        Ok(Some(report)) => HttpResponse::Ok().json(ApiResponse::success(report)),
+        Ok(None) => HttpResponse::NotFound()
+            .json(ApiResponse::<()>::error("No gap analysis found. Submit a job description first.")),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Database error: {}", e))),
+    }
+}
+
 /// Get current roadmap
 async fn get_roadmap(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     path: web::Path<String>,
 ) -> impl Responder {
-    let user_id = path.into_inner();
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
     
     let roadmaps = data.roadmaps.lock().unwrap();
     match roadmaps.get(&user_id) {
@@ -459,36 +1014,242 @@ async fn get_roadmap(
 /// Edit roadmap (HUMAN-IN-THE-LOOP)
 async fn edit_roadmap(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<EditRoadmapRequest>,
 ) -> impl Responder {
-    let mut roadmaps = data.roadmaps.lock().unwrap();
-    
-    match roadmaps.get_mut(&req.user_id) {
-        Some(roadmap) => {
-            match planner::apply_edit(roadmap, req.edit.clone()) {
-                Ok(description) => {
-                    // Record in memory
-                    let _ = memory::record_plan_modified(
-                        &data.memory_store,
-                        &req.user_id,
-                        &description,
-                    );
-                    HttpResponse::Ok().json(ApiResponse::success(roadmap.clone()))
-                }
-                Err(e) => HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e)),
-            }
+    let (user_id, author) = match authorize_author(&data, &http, &req.user_id) {
+        Ok(identity) => identity,
+        Err(resp) => return *resp,
+    };
+
+    match apply_versioned_edit(&data, &user_id, &author, req.edit.clone()) {
+        Ok((roadmap, description, _)) => {
+            // Record in memory
+            let _ = memory::record_plan_modified(&data.memory_store, &user_id, &description);
+            HttpResponse::Ok().json(ApiResponse::success(roadmap))
         }
-        None => HttpResponse::NotFound()
-            .json(ApiResponse::<()>::error("Roadmap not found")),
+        Err(resp) => *resp,
+    }
+}
+
+/// Apply an edit to the user's roadmap and save the result as a new version
+fn apply_versioned_edit(
+    data: &AppState,
+    user_id: &str,
+    author: &str,
+    edit: RoadmapEdit,
+) -> Result<(CareerRoadmap, String, PlanVersionSummary), Box<HttpResponse>> {
+    // Hold the user's lock from reading the base roadmap until the result is
+    // live, so a concurrent edit cannot be overwritten. The shared roadmap
+    // lock is only taken briefly, not while SQLite is written.
+    let user_lock = data.roadmap_lock(user_id);
+    let _guard = user_lock.lock().unwrap();
+
+    let (updated, description) = {
+        let roadmaps = data.roadmaps.lock().unwrap();
+        let mut updated = roadmaps.get(user_id).cloned().ok_or_else(|| {
+            Box::new(HttpResponse::NotFound().json(ApiResponse::<()>::error("Roadmap not found")))
+        })?;
+        let description = planner::apply_edit(&mut updated, edit.clone())
+            .map_err(|e| Box::new(HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e))))?;
+        (updated, description)
+    };
+
+    // A failed save leaves the current plan untouched
+    let version = data
+        .plan_versions
+        .save(user_id, author, &description, Some(&edit), &updated)
+        .map_err(|e| {
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ApiResponse::<()>::error(&format!("Database error: {}", e))),
+            )
+        })?;
+
+    data.roadmaps.lock().unwrap().insert(user_id.to_string(), updated.clone());
+    Ok((updated, description, version.summary()))
+}
+
+/// Like `authorize`, but also names who is acting: the session's username, else the user ID
+fn authorize_author(
+    data: &AppState,
+    http: &HttpRequest,
+    claimed_user_id: &str,
+) -> Result<(String, String), Box<HttpResponse>> {
+    let header_value = http
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    let (user_id, username) = data
+        .user_store
+        .resolve_identity(auth::bearer_token(header_value), Some(claimed_user_id))
+        .map_err(|e| Box::new(auth_error_response(&e)))?;
+    let author = username.unwrap_or_else(|| user_id.clone());
+    Ok((user_id, author))
+}
+
+/// Add a milestone to the roadmap (HUMAN-IN-THE-LOOP)
+async fn add_roadmap_step(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<AddStepRequest>,
+) -> impl Responder {
+    let (user_id, author) = match authorize_author(&data, &http, &path.into_inner()) {
+        Ok(identity) => identity,
+        Err(resp) => return *resp,
+    };
+
+    if req.title.trim().is_empty() {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Step title is required"));
+    }
+    let edit = RoadmapEdit::AddStep {
+        title: req.title.trim().to_string(),
+        description: req.description.clone().unwrap_or_default(),
+        step_type: req.step_type.clone().unwrap_or_else(|| "learn".to_string()),
+        after_step_id: req.after_step_id.clone(),
+    };
+
+    match apply_versioned_edit(&data, &user_id, &author, edit) {
+        Ok((roadmap, description, version)) => {
+            let _ = memory::record_plan_modified(&data.memory_store, &user_id, &description);
+            HttpResponse::Ok().json(ApiResponse::success(json!({ "roadmap": roadmap, "version": version })))
+        }
+        Err(resp) => *resp,
+    }
+}
+
+/// Remove a milestone from the roadmap (HUMAN-IN-THE-LOOP)
+async fn remove_roadmap_step(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (claimed_user_id, step_id) = path.into_inner();
+    let (user_id, author) = match authorize_author(&data, &http, &claimed_user_id) {
+        Ok(identity) => identity,
+        Err(resp) => return *resp,
+    };
+
+    match apply_versioned_edit(&data, &user_id, &author, RoadmapEdit::RemoveStep { step_id }) {
+        Ok((roadmap, description, version)) => {
+            let _ = memory::record_plan_modified(&data.memory_store, &user_id, &description);
+            HttpResponse::Ok().json(ApiResponse::success(json!({ "roadmap": roadmap, "version": version })))
+        }
+        Err(resp) => *resp,
+    }
+}
+
+/// Reorder all milestones at once (HUMAN-IN-THE-LOOP)
+async fn reorder_roadmap_steps(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<ReorderStepsRequest>,
+) -> impl Responder {
+    let (user_id, author) = match authorize_author(&data, &http, &path.into_inner()) {
+        Ok(identity) => identity,
+        Err(resp) => return *resp,
+    };
+
+    let edit = RoadmapEdit::SetOrder {
+        step_ids: req.step_ids.clone(),
+    };
+    match apply_versioned_edit(&data, &user_id, &author, edit) {
+        Ok((roadmap, description, version)) => {
+            let _ = memory::record_plan_modified(&data.memory_store, &user_id, &description);
+            HttpResponse::Ok().json(ApiResponse::success(json!({ "roadmap": roadmap, "version": version })))
+        }
+        Err(resp) => *resp,
     }
 }
 
+/// List every saved version of the roadmap
+async fn list_plan_versions(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.plan_versions.list(&user_id) {
+        Ok(versions) => HttpResponse::Ok().json(ApiResponse::success(versions)),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Database error: {}", e))),
+    }
+}
+
+/// Get one saved version of the roadmap
+async fn get_plan_version(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<(String, u32)>,
+) -> impl Responder {
+    let (claimed_user_id, version) = path.into_inner();
+    let user_id = match authorize(&data, &http, Some(&claimed_user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.plan_versions.get(&user_id, version) {
+        Ok(Some(v)) => HttpResponse::Ok().json(ApiResponse::success(v)),
+        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<()>::error("Version not found")),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Database error: {}", e))),
+    }
+}
+
+/// Diff two versions of the roadmap
+async fn diff_plan_versions(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<PlanDiffQuery>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let load = |version: Option<u32>| match version {
+        Some(v) => data.plan_versions.get(&user_id, v),
+        None => data.plan_versions.latest(&user_id),
+    };
+    let to = match load(query.to) {
+        Ok(Some(v)) => v,
+        Ok(None) => return HttpResponse::NotFound().json(ApiResponse::<()>::error("Version not found")),
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::error(&format!("Database error: {}", e)));
+        }
+    };
+    let from_version = query.from.unwrap_or_else(|| to.version.saturating_sub(1).max(1));
+    let from = match load(Some(from_version)) {
+        Ok(Some(v)) => v,
+        Ok(None) => return HttpResponse::NotFound().json(ApiResponse::<()>::error("Version not found")),
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::error(&format!("Database error: {}", e)));
+        }
+    };
+
+    HttpResponse::Ok().json(ApiResponse::success(plan_versions::diff_versions(&from, &to)))
+}
+
 /// Get agent memory timeline
 async fn get_memory(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     path: web::Path<String>,
 ) -> impl Responder {
-    let user_id = path.into_inner();
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
     
     match data.memory_store.get_user_memory(&user_id) {
         Ok(memory) => HttpResponse::Ok().json(ApiResponse::success(memory)),
@@ -497,12 +1258,76 @@ async fn get_memory(
     }
 }
 
+/// Get a filtered, paginated slice of the memory timeline
+async fn get_memory_events(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<MemoryQuery>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.memory_store.query_events(&user_id, &query) {
+        Ok(page) => HttpResponse::Ok().json(ApiResponse::success(page)),
+        Err(e @ MemoryQueryError::InvalidPhase(_)) => {
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e.to_string()))
+        }
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+/// Search a user's memory timeline by meaning rather than exact keywords
+async fn search_memory(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<MemorySearchQuery>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    if query.q.trim().is_empty() {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Query parameter 'q' is required"));
+    }
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+
+    // Embedding may call a remote API, so keep it off the async workers
+    let state = data.get_ref().clone();
+    let q = query.q.clone();
+    let result = web::block(move || {
+        state
+            .semantic_index
+            .search(&state.memory_store, &user_id, &q, limit)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(hits)) => HttpResponse::Ok().json(ApiResponse::success(hits)),
+        Ok(Err(e @ SearchError::Embedding(_))) => HttpResponse::BadGateway()
+            .json(ApiResponse::<()>::error(&format!("Memory search failed: {}", e))),
+        Ok(Err(e @ SearchError::Storage(_))) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Memory search failed: {}", e))),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&format!("Memory search failed: {}", e))),
+    }
+}
+
 /// Get latest weekly reflection
 async fn get_latest_reflection(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     path: web::Path<String>,
 ) -> impl Responder {
-    let user_id = path.into_inner();
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
     
     // Get memory
     let memory = match data.memory_store.get_user_memory(&user_id) {
@@ -525,17 +1350,263 @@ async fn get_latest_reflection(
         }
     };
 
-    // Generate reflection
-    let generator = ReflectionGenerator::new(ReflectionConfig::default());
-    let reflection = generator.generate_weekly_reflection(&memory, &roadmap);
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::ReflectionStarted, "Generating weekly reflection"));
+
+    // Generate reflection (off the async workers, since an LLM call blocks)
+    let mut generator = ReflectionGenerator::new(ReflectionConfig::default());
+    if let Some(provider) = &data.llm {
+        generator = generator.with_llm(provider.clone());
+    }
+    let reflection = match web::block(move || generator.generate_weekly_reflection(&memory, &roadmap)).await {
+        Ok(r) => r,
+        Err(e) => {
+            return failure_response(
+                &data,
+                &user_id,
+                HttpResponse::InternalServerError(),
+                &format!("Reflection generation failed: {}", e),
+            );
+        }
+    };
 
     // Store and record
     data.reflection_store.save_reflection(reflection.clone());
     let _ = memory::record_reflection(&data.memory_store, &user_id, &reflection.summary);
+    data.events.publish(
+        AgentEvent::new(&user_id, AgentEventKind::ReflectionComplete, &reflection.summary)
+            .with_data(json!({ "reflection_id": reflection.id, "scheduled": false })),
+    );
 
     HttpResponse::Ok().json(ApiResponse::success(reflection))
 }
 
+/// Get the user's weekly digest email preference
+async fn get_digest_subscription(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.digest_store.get(&user_id) {
+        Ok(Some(subscription)) => HttpResponse::Ok().json(ApiResponse::success(json!({
+            "subscription": subscription,
+            "delivery_configured": data.mailer.is_some(),
+        }))),
+        Ok(None) => HttpResponse::NotFound()
+            .json(ApiResponse::<()>::error("No digest subscription for this user")),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+/// Subscribe to (or update) the weekly digest email
+async fn set_digest_subscription(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<DigestSubscriptionRequest>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.digest_store.subscribe(&user_id, &req.email, req.enabled.unwrap_or(true)) {
+        Ok(subscription) => HttpResponse::Ok().json(ApiResponse::success(json!({
+            "subscription": subscription,
+            "delivery_configured": data.mailer.is_some(),
+        }))),
+        Err(e @ DigestError::InvalidEmail(_)) => {
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e.to_string()))
+        }
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+/// Stream live agent activity as server-sent events
+async fn stream_events(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<EventStreamQuery>,
+) -> impl Responder {
+    let user_id = match authorize_with_query_token(&data, &http, query.token.as_deref(), &path.into_inner()) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(data.events.stream_for_user(&user_id))
+}
+
+/// Authorize with a `?token=` query parameter, falling back to the `Authorization` header
+fn authorize_with_query_token(
+    data: &AppState,
+    http: &HttpRequest,
+    token: Option<&str>,
+    claimed_user_id: &str,
+) -> Result<String, Box<HttpResponse>> {
+    match token {
+        Some(token) => data
+            .user_store
+            .resolve_user(Some(token), Some(claimed_user_id))
+            .map_err(|e| Box::new(auth_error_response(&e))),
+        None => authorize(data, http, Some(claimed_user_id)),
+    }
+}
+
+/// Roadmap calendar for the signed-in user
+async fn roadmap_calendar(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<CalendarQuery>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    calendar_response(&data, &user_id, &query)
+}
+
+/// Subscribable iCalendar feed, authorized by a calendar feed token
+async fn calendar_feed(
+    data: web::Data<Arc<AppState>>,
+    path: web::Path<String>,
+    query: web::Query<CalendarQuery>,
+) -> impl Responder {
+    let user_id = match data.calendar_feeds.resolve(&path.into_inner()) {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(ApiResponse::<()>::error("Calendar feed not found"))
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::error(&e.to_string()))
+        }
+    };
+
+    calendar_response(&data, &user_id, &query)
+}
+
+fn calendar_response(data: &AppState, user_id: &str, query: &CalendarQuery) -> HttpResponse {
+    let mut options = CalendarOptions::default();
+    if let Some(day) = &query.day {
+        match day.parse() {
+            Ok(weekday) => options.learning_day = weekday,
+            Err(_) => {
+                return HttpResponse::BadRequest()
+                    .json(ApiResponse::<()>::error(&format!("Invalid day: {}", day)));
+            }
+        }
+    }
+    if let Some(hour) = query.hour {
+        if hour > 23 {
+            return HttpResponse::BadRequest().json(ApiResponse::<()>::error("hour must be 0-23"));
+        }
+        options.learning_hour = hour;
+    }
+    if let Some(minutes) = query.minutes {
+        if !(calendar::MIN_BLOCK_MINUTES..=calendar::MAX_BLOCK_MINUTES).contains(&minutes) {
+            return HttpResponse::BadRequest().json(ApiResponse::<()>::error(&format!(
+                "minutes must be {}-{}",
+                calendar::MIN_BLOCK_MINUTES,
+                calendar::MAX_BLOCK_MINUTES
+            )));
+        }
+        options.block_minutes = minutes;
+    }
+
+    // Render from a copy so the shared roadmap lock is not held meanwhile
+    let roadmap = match data.roadmaps.lock().unwrap().get(user_id).cloned() {
+        Some(r) => r,
+        None => return HttpResponse::NotFound().json(ApiResponse::<()>::error("Roadmap not found")),
+    };
+
+    match calendar::render_roadmap(&roadmap, &options, chrono::Utc::now()) {
+        Ok(ics) => HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            .insert_header((header::CONTENT_DISPOSITION, "inline; filename=\"career-roadmap.ics\""))
+            .body(ics),
+        Err(e) => HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+fn feed_json(feed: &CalendarFeed) -> serde_json::Value {
+    json!({
+        "token": feed.token,
+        "url": format!("/api/calendar/{}/roadmap.ics", feed.token),
+        "created_at": feed.created_at,
+    })
+}
+
+/// Get the user's calendar feed token
+async fn get_calendar_feed(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.calendar_feeds.get(&user_id) {
+        Ok(Some(feed)) => HttpResponse::Ok().json(ApiResponse::success(feed_json(&feed))),
+        Ok(None) => HttpResponse::NotFound()
+            .json(ApiResponse::<()>::error("No calendar feed for this user")),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+/// Issue a calendar feed token, revoking any previous one
+async fn issue_calendar_feed(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.calendar_feeds.issue(&user_id) {
+        Ok(feed) => HttpResponse::Ok().json(ApiResponse::success(feed_json(&feed))),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
+/// Revoke the user's calendar feed token
+async fn revoke_calendar_feed(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let user_id = match authorize(&data, &http, Some(&path.into_inner())) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    match data.calendar_feeds.revoke(&user_id) {
+        Ok(true) => HttpResponse::Ok().json(ApiResponse::success("Calendar feed revoked")),
+        Ok(false) => HttpResponse::NotFound()
+            .json(ApiResponse::<()>::error("No calendar feed for this user")),
+        Err(e) => HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
 /// Get all available career rules
 async fn get_career_rules(data: web::Data<Arc<AppState>>) -> impl Responder {
     HttpResponse::Ok().json(ApiResponse::success(data.career_rules.clone()))
@@ -544,36 +1615,40 @@ async fn get_career_rules(data: web::Data<Arc<AppState>>) -> impl Responder {
 /// Mark a step as completed
 async fn complete_step(
     data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     path: web::Path<(String, String)>,
 ) -> impl Responder {
-    let (user_id, step_id) = path.into_inner();
+    let (claimed_user_id, step_id) = path.into_inner();
+    let (user_id, author) = match authorize_author(&data, &http, &claimed_user_id) {
+        Ok(identity) => identity,
+        Err(resp) => return *resp,
+    };
     
-    let mut roadmaps = data.roadmaps.lock().unwrap();
-    match roadmaps.get_mut(&user_id) {
-        Some(roadmap) => {
-            let edit = RoadmapEdit::CompleteStep { step_id };
-            match planner::apply_edit(roadmap, edit) {
-                Ok(description) => {
-                    let _ = memory::record_step_completed(&data.memory_store, &user_id, &description);
-                    HttpResponse::Ok().json(ApiResponse::success(roadmap.clone()))
-                }
-                Err(e) => HttpResponse::BadRequest().json(ApiResponse::<()>::error(&e)),
-            }
+    match apply_versioned_edit(&data, &user_id, &author, RoadmapEdit::CompleteStep { step_id }) {
+        Ok((roadmap, description, _)) => {
+            let _ = memory::record_step_completed(&data.memory_store, &user_id, &description);
+            HttpResponse::Ok().json(ApiResponse::success(roadmap))
         }
-        None => HttpResponse::NotFound().json(ApiResponse::<()>::error("Roadmap not found")),
+        Err(resp) => *resp,
     }
 }
 
 /// Analyze resume and create agent session (full pipeline)
 async fn analyze_resume(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<UploadResumeRequest>,
 ) -> impl Responder {
     use crate::agent::resume_parser::{
-        ResumeParserConfig, full_pipeline, initialize_session,
+        ResumeParserConfig, analyze_parsed, initialize_session,
+    };
+
+    let user_id = match authorize(&data, &http, Some(&req.user_id)) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
     };
-    use std::io::Write;
 
-    println!("[analyze_resume] Starting analysis for user: {}", req.user_id);
+    println!("[analyze_resume] Starting analysis for user: {}", user_id);
 
     // Get raw text from request
     let raw_text = req.raw_text.clone().unwrap_or_default();
@@ -586,56 +1661,28 @@ async fn analyze_resume(
 
     println!("[analyze_resume] Received {} bytes of text", raw_text.len());
 
-    // Write to temp file for Python parser
-    // Use tempfile to generate short, safe filenames (avoids Windows MAX_PATH issues)
-    let mut temp_file = match tempfile::Builder::new()
-        .prefix("resume_")
-        .suffix(".txt")
-        .tempfile()
-    {
-        Ok(f) => f,
-        Err(e) => {
-            println!("[analyze_resume] ERROR: Failed to create temp file: {}", e);
-            return HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error(&format!("Failed to create temp file: {}", e)));
-        }
-    };
-    
-    let temp_path = temp_file.path().to_path_buf();
-    println!("[analyze_resume] Writing to temp file: {:?}", temp_path);
-    
-    if let Err(e) = temp_file.write_all(raw_text.as_bytes()) {
-        println!("[analyze_resume] ERROR: Failed to write temp file: {}", e);
-        return HttpResponse::InternalServerError()
-            .json(ApiResponse::<()>::error(&format!("Failed to write temp file: {}", e)));
-    }
+    // Parse natively; the evidence, bottleneck and strategy stages that
+    // follow still run the Python scripts in ../resume_parser/
+    let parsed = native_parser::parse_resume_text(&raw_text);
+    let config = ResumeParserConfig::default();
 
-    // Configure parser with correct paths
-    // The server runs from backend/, scripts are in ../resume_parser/
-    // Use relative paths that will be resolved by ResumeParserConfig::resolve_path()
-    let config = ResumeParserConfig {
-        python_path: "../resume_parser/venv/Scripts/python.exe".to_string(),
-        script_path: "../resume_parser/parser.py".to_string(),
-    };
-    
     eprintln!("[analyze_resume] Config paths (relative):");
     eprintln!("  Python: {}", config.python_path);
     eprintln!("  Script: {}", config.script_path);
     eprintln!("  Current dir: {:?}", std::env::current_dir());
 
-    // Run full pipeline
-    // Note: temp_file is kept alive until pipeline completes, then auto-deleted on drop
+    // Run the analysis stages
     println!("[analyze_resume] Running full pipeline...");
-    match full_pipeline(temp_path.to_str().unwrap_or(""), &config) {
+    data.events.publish(AgentEvent::new(&user_id, AgentEventKind::SensingStarted, "Analyzing resume"));
+    match analyze_parsed(parsed, &config) {
         Ok((parsed, evidence, bottleneck, strategy)) => {
             println!("[analyze_resume] Pipeline succeeded!");
             println!("[analyze_resume] Strategy: {:?}", strategy.strategy);
-            
-            // temp_file auto-deletes on drop
 
             // Initialize agent session
             let session = initialize_session(evidence, bottleneck, strategy);
             println!("[analyze_resume] Session initialized");
+            data.events.publish(AgentEvent::new(&user_id, AgentEventKind::SensingComplete, "Resume analysis complete"));
 
             // Return session
             HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
@@ -645,9 +1692,8 @@ async fn analyze_resume(
         }
         Err(e) => {
             println!("[analyze_resume] ERROR: Pipeline failed: {:?}", e);
-            
-            // temp_file auto-deletes on drop
-            
+            data.events.publish(AgentEvent::new(&user_id, AgentEventKind::Failed, &e.to_string()));
+
             HttpResponse::InternalServerError()
                 .json(ApiResponse::<()>::error(&format!("Pipeline error: {}", e)))
         }
@@ -656,10 +1702,19 @@ async fn analyze_resume(
 
 /// Process outcome through agent loop
 async fn process_outcome_handler(
+    data: web::Data<Arc<AppState>>,
+    http: HttpRequest,
     req: web::Json<OutcomeRequest>,
 ) -> impl Responder {
     use crate::agent::resume_parser::{AgentSession, ResumeParserConfig, process_outcome};
 
+    // The session travels with the request, so only check that the caller is allowed in
+    if data.user_store.config().require_auth {
+        if let Err(resp) = authorize(&data, &http, None) {
+            return *resp;
+        }
+    }
+
     // Validate outcome
     let outcome = req.outcome.as_str();
     if !matches!(outcome, "no_response" | "rejected" | "interview") {
@@ -709,17 +1764,53 @@ pub async fn run_server(host: &str, port: u16) -> std::io::Result<()> {
     let state = Arc::new(AppState::new().expect("Failed to initialize app state"));
 
     println!("🚀 Career Agent API starting at http://{}:{}", host, port);
+    match &state.llm {
+        Some(provider) => println!("🤖 LLM provider: {} ({})", provider.name(), provider.model()),
+        None => println!("🤖 LLM provider: none (rule-based text)"),
+    }
+
+    let scheduler_config = SchedulerConfig::from_env();
+    if scheduler_config.enabled {
+        println!(
+            "🗓️  Weekly reflections scheduled every {}h (email digests {})",
+            scheduler_config.interval_hours,
+            if state.mailer.is_some() { "on" } else { "off" }
+        );
+    }
+    scheduler::spawn(state.clone(), scheduler_config);
     println!("📚 API Endpoints:");
+    println!("   POST /api/auth/register   - Register a user");
+    println!("   POST /api/auth/login      - Log in (returns bearer token)");
+    println!("   POST /api/auth/logout     - End session");
+    println!("   GET  /api/auth/me         - Current user");
     println!("   POST /api/resume          - Upload resume");
+    println!("   POST /api/resume/upload   - Upload resume file (PDF/DOCX/TXT)");
     println!("   POST /api/analyze         - Analyze resume (full pipeline)");
     println!("   POST /api/goal            - Set career goal");
+    println!("   POST /api/gap             - Analyze job description gap");
+    println!("   GET  /api/gap/:id         - Get latest gap report");
     println!("   POST /api/roadmap         - Generate roadmap");
     println!("   GET  /api/roadmap/:id     - Get roadmap");
     println!("   POST /api/roadmap/edit    - Edit roadmap");
+    println!("   POST /api/roadmap/:id/steps - Add step");
+    println!("   DELETE /api/roadmap/:id/steps/:step - Remove step");
+    println!("   PUT  /api/roadmap/:id/order - Reorder steps");
+    println!("   GET  /api/roadmap/:id/versions - Roadmap version history");
+    println!("   GET  /api/roadmap/:id/diff - Diff roadmap versions");
+    println!("   GET  /api/roadmap/:id/calendar.ics - Roadmap calendar");
+    println!("   POST /api/roadmap/:id/calendar/feed - Issue calendar feed token");
+    println!("   GET  /api/roadmap/:id/calendar/feed - Get calendar feed token");
+    println!("   DELETE /api/roadmap/:id/calendar/feed - Revoke calendar feed token");
+    println!("   GET  /api/calendar/:token/roadmap.ics - Calendar subscription feed");
     println!("   POST /api/outcome         - Process outcome");
     println!("   GET  /api/memory/:id      - Get memory timeline");
+    println!("   GET  /api/memory/:id/events - Query memory (filter/paginate)");
+    println!("   GET  /api/memory/:id/search - Semantic memory search (?q=)");
     println!("   GET  /api/reflection/:id  - Get weekly reflection");
+    println!("   GET  /api/reflection/:id/digest - Get digest email preference");
+    println!("   PUT  /api/reflection/:id/digest - Set digest email preference");
     println!("   GET  /api/rules           - Get career rules");
+    println!("   GET  /api/events/:id      - Live agent activity (SSE)");
 
     HttpServer::new(move || {
         let cors = Cors::default()
@@ -731,16 +1822,43 @@ pub async fn run_server(host: &str, port: u16) -> std::io::Result<()> {
             .wrap(cors)
             .app_data(web::Data::new(state.clone()))
             .route("/health", web::get().to(health_check))
+            .route("/api/auth/register", web::post().to(register))
+            .route("/api/auth/login", web::post().to(login))
+            .route("/api/auth/logout", web::post().to(logout))
+            .route("/api/auth/me", web::get().to(current_user))
             .route("/api/resume", web::post().to(upload_resume))
+            .service(
+                web::resource("/api/resume/upload")
+                    .app_data(web::PayloadConfig::new(MAX_RESUME_BYTES))
+                    .route(web::post().to(upload_resume_file)),
+            )
             .route("/api/analyze", web::post().to(analyze_resume))
             .route("/api/goal", web::post().to(set_goal))
+            .route("/api/gap", web::post().to(analyze_job_gap))
+            .route("/api/gap/{user_id}", web::get().to(get_job_gap))
             .route("/api/roadmap", web::post().to(generate_roadmap))
             .route("/api/roadmap/{user_id}", web::get().to(get_roadmap))
             .route("/api/roadmap/edit", web::post().to(edit_roadmap))
             .route("/api/roadmap/{user_id}/step/{step_id}/complete", web::post().to(complete_step))
+            .route("/api/roadmap/{user_id}/steps", web::post().to(add_roadmap_step))
+            .route("/api/roadmap/{user_id}/steps/{step_id}", web::delete().to(remove_roadmap_step))
+            .route("/api/roadmap/{user_id}/order", web::put().to(reorder_roadmap_steps))
+            .route("/api/roadmap/{user_id}/versions", web::get().to(list_plan_versions))
+            .route("/api/roadmap/{user_id}/versions/{version}", web::get().to(get_plan_version))
+            .route("/api/roadmap/{user_id}/diff", web::get().to(diff_plan_versions))
+            .route("/api/roadmap/{user_id}/calendar.ics", web::get().to(roadmap_calendar))
+            .route("/api/roadmap/{user_id}/calendar/feed", web::post().to(issue_calendar_feed))
+            .route("/api/roadmap/{user_id}/calendar/feed", web::get().to(get_calendar_feed))
+            .route("/api/roadmap/{user_id}/calendar/feed", web::delete().to(revoke_calendar_feed))
+            .route("/api/calendar/{token}/roadmap.ics", web::get().to(calendar_feed))
             .route("/api/outcome", web::post().to(process_outcome_handler))
             .route("/api/memory/{user_id}", web::get().to(get_memory))
+            .route("/api/memory/{user_id}/events", web::get().to(get_memory_events))
+            .route("/api/memory/{user_id}/search", web::get().to(search_memory))
             .route("/api/reflection/{user_id}", web::get().to(get_latest_reflection))
+            .route("/api/events/{user_id}", web::get().to(stream_events))
+            .route("/api/reflection/{user_id}/digest", web::get().to(get_digest_subscription))
+            .route("/api/reflection/{user_id}/digest", web::put().to(set_digest_subscription))
             .route("/api/rules", web::get().to(get_career_rules))
     })
     .bind((host, port))?
@@ -0,0 +1,430 @@
+//! Authentication Module
+//!
+//! User accounts and bearer-token sessions for the Career Agent API.
+//! Users and sessions live in the same SQLite database as the memory
+//! timeline, so every per-user record is scoped by the authenticated user ID.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock};
+use thiserror::Error;
+use uuid::Uuid;
+
+// ============================================================
+// CONFIGURATION
+// ============================================================
+
+/// Authentication settings
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// Reject requests without a valid bearer token.
+    /// When false (prototype mode) a client-supplied `user_id` is trusted without
+    /// a token, except for IDs of registered accounts; a sent token always wins.
+    pub require_auth: bool,
+    /// How long a login session stays valid
+    pub session_ttl_hours: i64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            require_auth: false,
+            session_ttl_hours: 24 * 7,
+        }
+    }
+}
+
+impl AuthConfig {
+    /// Longest accepted session lifetime (one year)
+    pub const MAX_SESSION_TTL_HOURS: i64 = 24 * 365;
+
+    /// Read `CAREER_AGENT_REQUIRE_AUTH` and `CAREER_AGENT_SESSION_TTL_HOURS`
+    /// (1 to `MAX_SESSION_TTL_HOURS`; other values fall back to the default)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            require_auth: std::env::var("CAREER_AGENT_REQUIRE_AUTH")
+                .map(|v| matches!(v.as_str(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.require_auth),
+            session_ttl_hours: std::env::var("CAREER_AGENT_SESSION_TTL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|hours| (1..=Self::MAX_SESSION_TTL_HOURS).contains(hours))
+                .unwrap_or(defaults.session_ttl_hours),
+        }
+    }
+}
+
+// ============================================================
+// TYPES
+// ============================================================
+
+/// A registered user (never includes the password hash)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An active login session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub token: String,
+    pub user_id: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Username already taken")]
+    UsernameTaken,
+
+    #[error("Invalid username or password")]
+    InvalidCredentials,
+
+    #[error("Missing or invalid session token")]
+    Unauthorized,
+
+    #[error("Not allowed to access another user's data")]
+    Forbidden,
+
+    #[error("Invalid registration: {0}")]
+    Invalid(String),
+
+    #[error("Password hashing failed: {0}")]
+    Hashing(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+}
+
+// ============================================================
+// USER STORE
+// ============================================================
+
+/// SQLite-backed users and sessions
+pub struct UserStore {
+    conn: Arc<Mutex<Connection>>,
+    config: AuthConfig,
+}
+
+impl UserStore {
+    /// Create the store on an existing connection (shared with `MemoryStore`)
+    pub fn new(conn: Arc<Mutex<Connection>>, config: AuthConfig) -> rusqlite::Result<Self> {
+        {
+            let conn = conn.lock().unwrap();
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS users (
+                    id TEXT PRIMARY KEY,
+                    username TEXT NOT NULL UNIQUE,
+                    password_hash TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                )",
+                [],
+            )?;
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS sessions (
+                    token TEXT PRIMARY KEY,
+                    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    expires_at TEXT NOT NULL
+                )",
+                [],
+            )?;
+        }
+
+        Ok(Self { conn, config })
+    }
+
+    pub fn config(&self) -> &AuthConfig {
+        &self.config
+    }
+
+    /// Register a new user and open a session for them
+    pub fn register(&self, username: &str, password: &str) -> Result<(User, Session), AuthError> {
+        let username = username.trim();
+        if username.is_empty() {
+            return Err(AuthError::Invalid("username is required".to_string()));
+        }
+        if password.len() < 8 {
+            return Err(AuthError::Invalid(
+                "password must be at least 8 characters".to_string(),
+            ));
+        }
+
+        let user = User {
+            id: Uuid::new_v4().to_string(),
+            username: username.to_string(),
+            created_at: Utc::now(),
+        };
+        let password_hash = hash_password(password)?;
+
+        {
+            // The UNIQUE constraint settles concurrent registrations for the same name
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO users (id, username, password_hash, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![user.id, user.username, password_hash, user.created_at.to_rfc3339()],
+            )
+            .map_err(|e| match e.sqlite_error_code() {
+                Some(rusqlite::ErrorCode::ConstraintViolation) => AuthError::UsernameTaken,
+                _ => AuthError::Database(e),
+            })?;
+        }
+
+        let session = self.create_session(&user.id)?;
+        Ok((user, session))
+    }
+
+    /// Check credentials and open a new session
+    pub fn login(&self, username: &str, password: &str) -> Result<(User, Session), AuthError> {
+        let row: Option<(String, String, String)> = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT id, password_hash, created_at FROM users WHERE username = ?1",
+                [username.trim()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+        };
+
+        let (id, password_hash, created_at) = match row {
+            Some(row) => row,
+            None => {
+                // Spend the same Argon2 time as a real check so unknown
+                // usernames cannot be told apart by response time
+                verify_password(password, dummy_hash());
+                return Err(AuthError::InvalidCredentials);
+            }
+        };
+        if !verify_password(password, &password_hash) {
+            return Err(AuthError::InvalidCredentials);
+        }
+
+        let user = User {
+            id,
+            username: username.trim().to_string(),
+            created_at: parse_timestamp(&created_at),
+        };
+        let session = self.create_session(&user.id)?;
+        Ok((user, session))
+    }
+
+    /// End a session
+    pub fn logout(&self, token: &str) -> Result<(), AuthError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM sessions WHERE token = ?1", [token])?;
+        Ok(())
+    }
+
+    /// Resolve a session token to its user, if the session is still valid
+    pub fn authenticate(&self, token: &str) -> Result<User, AuthError> {
+        let conn = self.conn.lock().unwrap();
+        let row: Option<(String, String, String, String)> = conn
+            .query_row(
+                "SELECT u.id, u.username, u.created_at, s.expires_at
+                 FROM sessions s JOIN users u ON u.id = s.user_id
+                 WHERE s.token = ?1",
+                [token],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?;
+
+        let (id, username, created_at, expires_at) = row.ok_or(AuthError::Unauthorized)?;
+        if parse_timestamp(&expires_at) <= Utc::now() {
+            conn.execute("DELETE FROM sessions WHERE token = ?1", [token])?;
+            return Err(AuthError::Unauthorized);
+        }
+
+        Ok(User {
+            id,
+            username,
+            created_at: parse_timestamp(&created_at),
+        })
+    }
+
+    /// Work out which user a request acts as.
+    ///
+    /// A valid token always determines the user; a `claimed_user_id` that
+    /// disagrees with it is rejected. Without a token the claimed ID is only
+    /// accepted when `require_auth` is off and it is not a registered account.
+    pub fn resolve_user(
+        &self,
+        token: Option<&str>,
+        claimed_user_id: Option<&str>,
+    ) -> Result<String, AuthError> {
+        self.resolve_identity(token, claimed_user_id).map(|(id, _)| id)
+    }
+
+    /// Like `resolve_user`, but also returns the username when a valid token was sent
+    pub fn resolve_identity(
+        &self,
+        token: Option<&str>,
+        claimed_user_id: Option<&str>,
+    ) -> Result<(String, Option<String>), AuthError> {
+        match token {
+            Some(token) => {
+                let user = self.authenticate(token)?;
+                match claimed_user_id {
+                    Some(claimed) if claimed != user.id => Err(AuthError::Forbidden),
+                    _ => Ok((user.id, Some(user.username))),
+                }
+            }
+            None if self.config.require_auth => Err(AuthError::Unauthorized),
+            None => {
+                let claimed = claimed_user_id.ok_or(AuthError::Unauthorized)?;
+                if self.is_registered(claimed)? {
+                    return Err(AuthError::Unauthorized);
+                }
+                Ok((claimed.to_string(), None))
+            }
+        }
+    }
+
+    /// Whether a user ID belongs to a registered account
+    fn is_registered(&self, user_id: &str) -> Result<bool, AuthError> {
+        let conn = self.conn.lock().unwrap();
+        let found: Option<i64> = conn
+            .query_row("SELECT 1 FROM users WHERE id = ?1", [user_id], |row| row.get(0))
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    fn create_session(&self, user_id: &str) -> Result<Session, AuthError> {
+        let session = Session {
+            token: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+            user_id: user_id.to_string(),
+            expires_at: Utc::now() + Duration::hours(self.config.session_ttl_hours),
+        };
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM sessions WHERE expires_at <= ?1",
+            [Utc::now().to_rfc3339()],
+        )?;
+        conn.execute(
+            "INSERT INTO sessions (token, user_id, expires_at) VALUES (?1, ?2, ?3)",
+            params![session.token, session.user_id, session.expires_at.to_rfc3339()],
+        )?;
+
+        Ok(session)
+    }
+}
+
+// ============================================================
+// HELPER FUNCTIONS
+// ============================================================
+
+/// Extract the token from an `Authorization: Bearer <token>` header value
+pub fn bearer_token(header: Option<&str>) -> Option<&str> {
+    header
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+fn hash_password(password: &str) -> Result<String, AuthError> {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+        .map_err(|e| AuthError::Hashing(e.to_string()))?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| AuthError::Hashing(e.to_string()))
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Hash checked when a username does not exist, computed once
+fn dummy_hash() -> &'static str {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    DUMMY_HASH.get_or_init(|| hash_password("career-agent-dummy-password").unwrap_or_default())
+}
+
+fn parse_timestamp(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_store(require_auth: bool) -> UserStore {
+        let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        UserStore::new(
+            conn,
+            AuthConfig {
+                require_auth,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_register_login_and_authenticate() {
+        let store = test_store(true);
+
+        let (user, session) = store.register("alice", "correct horse").unwrap();
+        assert_eq!(store.authenticate(&session.token).unwrap().id, user.id);
+
+        assert!(matches!(
+            store.register("alice", "another password"),
+            Err(AuthError::UsernameTaken)
+        ));
+        assert!(matches!(
+            store.login("alice", "wrong password"),
+            Err(AuthError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            store.login("nobody", "correct horse"),
+            Err(AuthError::InvalidCredentials)
+        ));
+
+        let (_, second) = store.login("alice", "correct horse").unwrap();
+        store.logout(&second.token).unwrap();
+        assert!(store.authenticate(&second.token).is_err());
+    }
+
+    #[test]
+    fn test_resolve_user_scopes_requests() {
+        let store = test_store(true);
+        let (alice, session) = store.register("alice", "correct horse").unwrap();
+        let (bob, _) = store.register("bob", "battery staple").unwrap();
+
+        assert_eq!(
+            store.resolve_user(Some(&session.token), None).unwrap(),
+            alice.id
+        );
+        assert!(matches!(
+            store.resolve_user(Some(&session.token), Some(&bob.id)),
+            Err(AuthError::Forbidden)
+        ));
+        assert!(matches!(
+            store.resolve_user(None, Some(&alice.id)),
+            Err(AuthError::Unauthorized)
+        ));
+
+        let prototype = test_store(false);
+        assert_eq!(prototype.resolve_user(None, Some("demo")).unwrap(), "demo");
+        // Registered accounts need their token even in prototype mode
+        let (carol, _) = prototype.register("carol", "correct horse").unwrap();
+        assert!(matches!(
+            prototype.resolve_user(None, Some(&carol.id)),
+            Err(AuthError::Unauthorized)
+        ));
+    }
+}
@@ -0,0 +1,447 @@
+//! Roadmap Calendar Module
+//!
+//! Renders a career roadmap as an iCalendar (RFC 5545) feed: one all-day
+//! milestone per step on its target date, plus a recurring weekly learning
+//! block while the step is in progress. Calendar apps subscribe to the feed
+//! URL and pick up plan edits on their next refresh.
+//!
+//! Subscription URLs carry a dedicated feed token rather than a session
+//! token: it only grants read access to the calendar, does not expire with
+//! the login session, and can be revoked or rotated on its own.
+
+use crate::agent::planner::{CareerRoadmap, RoadmapStep};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Product identifier written into every feed
+pub const PRODID: &str = "-//Career Agent//Career Roadmap//EN";
+
+/// How often subscribed calendars should re-fetch the feed
+pub const REFRESH_INTERVAL: &str = "PT12H";
+
+/// Allowed length of a learning block, in minutes
+pub const MIN_BLOCK_MINUTES: u32 = 15;
+pub const MAX_BLOCK_MINUTES: u32 = 8 * 60;
+
+/// Maximum octets per content line before folding
+const MAX_LINE_OCTETS: usize = 75;
+
+// ============================================================
+// OPTIONS
+// ============================================================
+
+/// Why a roadmap could not be rendered
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CalendarError {
+    /// The steps' combined duration runs past the last representable date
+    #[error("Roadmap schedule runs past the supported date range")]
+    DateOutOfRange,
+}
+
+/// When the weekly learning blocks are scheduled
+#[derive(Debug, Clone)]
+pub struct CalendarOptions {
+    /// First day of the plan (defaults to the roadmap's generation date)
+    pub start_date: Option<NaiveDate>,
+    pub learning_day: Weekday,
+    /// Local start hour of the learning block (0-23)
+    pub learning_hour: u32,
+    /// Clamped to `MIN_BLOCK_MINUTES..=MAX_BLOCK_MINUTES`
+    pub block_minutes: u32,
+}
+
+impl Default for CalendarOptions {
+    fn default() -> Self {
+        Self {
+            start_date: None,
+            learning_day: Weekday::Sat,
+            learning_hour: 10,
+            block_minutes: 90,
+        }
+    }
+}
+
+// ============================================================
+// SCHEDULE
+// ============================================================
+
+/// Dates a step occupies on the plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepWindow {
+    pub step_id: String,
+    pub start: NaiveDate,
+    /// Milestone date (end of the step's last week)
+    pub due: NaiveDate,
+    pub weeks: u32,
+}
+
+/// Lay steps end to end from `start`, in plan order, skipping skipped steps
+pub fn schedule_steps(roadmap: &CareerRoadmap, start: NaiveDate) -> Result<Vec<StepWindow>, CalendarError> {
+    let mut steps: Vec<&RoadmapStep> = roadmap.steps.iter().filter(|s| s.status != "skipped").collect();
+    steps.sort_by_key(|s| s.order);
+
+    let mut cursor = start;
+    steps
+        .into_iter()
+        .map(|step| {
+            let weeks = step.estimated_weeks.max(1);
+            let next = cursor
+                .checked_add_signed(Duration::weeks(weeks as i64))
+                .ok_or(CalendarError::DateOutOfRange)?;
+            let window = StepWindow {
+                step_id: step.id.clone(),
+                start: cursor,
+                due: next - Duration::days(1),
+                weeks,
+            };
+            cursor = next;
+            Ok(window)
+        })
+        .collect()
+}
+
+/// First `day` on or after `date`
+fn next_weekday(date: NaiveDate, day: Weekday) -> Option<NaiveDate> {
+    let ahead = (7 + day.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+    date.checked_add_signed(Duration::days(ahead as i64))
+}
+
+// ============================================================
+// RENDERING
+// ============================================================
+
+/// Render the roadmap as an `.ics` document
+pub fn render_roadmap(
+    roadmap: &CareerRoadmap,
+    options: &CalendarOptions,
+    now: DateTime<Utc>,
+) -> Result<String, CalendarError> {
+    let start = options.start_date.unwrap_or_else(|| roadmap.generated_at.date_naive());
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&format!("Career roadmap: {}", roadmap.goal.description))),
+        format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH_INTERVAL),
+        format!("X-PUBLISHED-TTL:{}", REFRESH_INTERVAL),
+    ];
+
+    for window in schedule_steps(roadmap, start)? {
+        let step = match roadmap.steps.iter().find(|s| s.id == window.step_id) {
+            Some(s) => s,
+            None => continue,
+        };
+        let completed = step.status == "completed";
+
+        // Milestone: all-day event on the due date with a reminder the day before
+        let summary = if completed {
+            format!("✓ Milestone: {}", step.title)
+        } else {
+            format!("Milestone: {}", step.title)
+        };
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-milestone@career-agent", step.id),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", window.due.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", (window.due + Duration::days(1)).format("%Y%m%d")),
+            format!("SUMMARY:{}", escape_text(&summary)),
+            format!("DESCRIPTION:{}", escape_text(&milestone_description(step))),
+            format!("CATEGORIES:{}", escape_text(&step.step_type)),
+            "TRANSP:TRANSPARENT".to_string(),
+        ]);
+        if !completed {
+            lines.extend(alarm("-P1D", &format!("Milestone due tomorrow: {}", step.title)));
+        }
+        lines.push("END:VEVENT".to_string());
+
+        // Finished steps need no more study time
+        if completed {
+            continue;
+        }
+
+        // Weekly learning block, repeating for each week of the step
+        let first = match next_weekday(window.start, options.learning_day) {
+            Some(first) if first <= window.due => first,
+            _ => continue,
+        };
+        let begins = first.and_hms_opt(options.learning_hour.min(23), 0, 0).unwrap_or_default();
+        let ends = begins + Duration::minutes(options.block_minutes.clamp(MIN_BLOCK_MINUTES, MAX_BLOCK_MINUTES) as i64);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-learning@career-agent", step.id),
+            format!("DTSTAMP:{}", stamp),
+            // Floating local time so the block follows the subscriber's time zone
+            format!("DTSTART:{}", begins.format("%Y%m%dT%H%M%S")),
+            format!("DTEND:{}", ends.format("%Y%m%dT%H%M%S")),
+            // UNTIL must match DTSTART's value type, so use the end of the due date
+            format!("RRULE:FREQ=WEEKLY;UNTIL={}T235959", window.due.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape_text(&format!("Learning block: {}", step.title))),
+            format!("DESCRIPTION:{}", escape_text(&step.description)),
+        ]);
+        lines.extend(alarm("-PT15M", &format!("Learning block: {}", step.title)));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    Ok(lines.iter().map(|l| fold_line(l)).collect::<Vec<_>>().join(""))
+}
+
+fn milestone_description(step: &RoadmapStep) -> String {
+    let mut description = step.description.clone();
+    if !step.reason.is_empty() {
+        description.push_str(&format!("\n\nWhy: {}", step.reason));
+    }
+    if !step.resources.is_empty() {
+        description.push_str(&format!("\n\nResources:\n{}", step.resources.join("\n")));
+    }
+    description
+}
+
+fn alarm(trigger: &str, description: &str) -> Vec<String> {
+    vec![
+        "BEGIN:VALARM".to_string(),
+        "ACTION:DISPLAY".to_string(),
+        format!("TRIGGER:{}", trigger),
+        format!("DESCRIPTION:{}", escape_text(description)),
+        "END:VALARM".to_string(),
+    ]
+}
+
+/// Escape a TEXT property value
+pub fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets (never inside a UTF-8 character) and terminate it with CRLF
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+// ============================================================
+// FEED TOKENS
+// ============================================================
+
+/// A read-only subscription token for one user's calendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFeed {
+    pub token: String,
+    pub user_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// SQLite-backed feed tokens, at most one per user (shares the memory database)
+pub struct CalendarFeedStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CalendarFeedStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> SqlResult<Self> {
+        conn.lock().unwrap().execute(
+            "CREATE TABLE IF NOT EXISTS calendar_feeds (
+                token TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Issue a new feed token, revoking the user's previous one
+    pub fn issue(&self, user_id: &str) -> SqlResult<CalendarFeed> {
+        let feed = CalendarFeed {
+            token: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+            user_id: user_id.to_string(),
+            created_at: Utc::now(),
+        };
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO calendar_feeds (token, user_id, created_at) VALUES (?1, ?2, ?3)",
+            params![feed.token, feed.user_id, feed.created_at.to_rfc3339()],
+        )?;
+        Ok(feed)
+    }
+
+    pub fn get(&self, user_id: &str) -> SqlResult<Option<CalendarFeed>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT token, user_id, created_at FROM calendar_feeds WHERE user_id = ?1",
+                [user_id],
+                row_to_feed,
+            )
+            .optional()
+    }
+
+    /// User a feed token belongs to, if it has not been revoked
+    pub fn resolve(&self, token: &str) -> SqlResult<Option<String>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT user_id FROM calendar_feeds WHERE token = ?1",
+                [token],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Revoke the user's feed token; returns whether one existed
+    pub fn revoke(&self, user_id: &str) -> SqlResult<bool> {
+        let deleted = self
+            .conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM calendar_feeds WHERE user_id = ?1", [user_id])?;
+        Ok(deleted > 0)
+    }
+}
+
+fn row_to_feed(row: &rusqlite::Row<'_>) -> SqlResult<CalendarFeed> {
+    let created_at: String = row.get(2)?;
+    Ok(CalendarFeed {
+        token: row.get(0)?,
+        user_id: row.get(1)?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::planner::{PlanningGoal, RoadmapMetadata};
+
+    fn test_roadmap() -> CareerRoadmap {
+        let mut first = RoadmapStep::new(1, "Learn SQL", "learn");
+        first.estimated_weeks = 2;
+        first.description = "Joins, indexes; window functions".to_string();
+        let mut skipped = RoadmapStep::new(2, "Optional detour", "practice");
+        skipped.status = "skipped".to_string();
+        let mut second = RoadmapStep::new(3, "Build a portfolio project", "apply");
+        second.estimated_weeks = 3;
+
+        CareerRoadmap {
+            id: "r1".to_string(),
+            user_id: "u1".to_string(),
+            goal: PlanningGoal::role_transition("Data Analyst", 6),
+            generated_at: Utc::now(),
+            steps: vec![second, skipped, first],
+            deferred: Vec::new(),
+            requires_human_approval: true,
+            metadata: RoadmapMetadata {
+                llm_assisted: false,
+                llm_model: None,
+                warnings: Vec::new(),
+                steps_considered: 3,
+                generation_duration_ms: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_schedule_steps() {
+        let roadmap = test_roadmap();
+        // A Monday
+        let start = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let windows = schedule_steps(&roadmap, start).unwrap();
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].start, start);
+        assert_eq!(windows[0].due, NaiveDate::from_ymd_opt(2026, 10, 25).unwrap());
+        assert_eq!(windows[1].start, NaiveDate::from_ymd_opt(2026, 10, 26).unwrap());
+        assert_eq!(windows[1].weeks, 3);
+        assert_eq!(next_weekday(start, Weekday::Sat), NaiveDate::from_ymd_opt(2026, 10, 17));
+        assert_eq!(next_weekday(start, Weekday::Mon), Some(start));
+    }
+
+    #[test]
+    fn test_render_roadmap() {
+        let mut roadmap = test_roadmap();
+        roadmap.steps[0].status = "completed".to_string();
+        let options = CalendarOptions {
+            start_date: NaiveDate::from_ymd_opt(2026, 10, 12),
+            ..Default::default()
+        };
+        let ics = render_roadmap(&roadmap, &options, Utc::now()).unwrap();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261025\r\n"));
+        assert!(ics.contains("DTSTART:20261017T100000\r\n"));
+        assert!(ics.contains("RRULE:FREQ=WEEKLY;UNTIL=20261025T235959\r\n"));
+        assert!(ics.contains("SUMMARY:✓ Milestone: Build a portfolio project\r\n"));
+        assert!(ics.contains("Joins\\, indexes\\; window functions"));
+        assert!(!ics.contains("Optional detour"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+
+        // Oversized blocks are capped
+        let options = CalendarOptions {
+            block_minutes: 4_000_000_000,
+            ..options
+        };
+        let ics = render_roadmap(&roadmap, &options, Utc::now()).unwrap();
+        assert!(ics.contains("DTEND:20261017T180000\r\n"));
+    }
+
+    #[test]
+    fn test_render_rejects_schedule_past_date_range() {
+        let mut roadmap = test_roadmap();
+        for step in &mut roadmap.steps {
+            step.estimated_weeks = 4_000_000_000;
+        }
+        let options = CalendarOptions {
+            start_date: NaiveDate::from_ymd_opt(2026, 10, 12),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render_roadmap(&roadmap, &options, Utc::now()),
+            Err(CalendarError::DateOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_feed_tokens_rotate_and_revoke() {
+        let store = CalendarFeedStore::new(Arc::new(Mutex::new(Connection::open_in_memory().unwrap()))).unwrap();
+
+        let first = store.issue("u1").unwrap();
+        assert_eq!(store.resolve(&first.token).unwrap().as_deref(), Some("u1"));
+
+        let second = store.issue("u1").unwrap();
+        assert!(store.resolve(&first.token).unwrap().is_none());
+        assert_eq!(store.get("u1").unwrap().unwrap().token, second.token);
+
+        assert!(store.revoke("u1").unwrap());
+        assert!(store.resolve(&second.token).unwrap().is_none());
+        assert!(!store.revoke("u1").unwrap());
+    }
+}
@@ -0,0 +1,234 @@
+//! Agent Events Module
+//!
+//! Live activity feed for the Sense → Plan → Learn loop. Handlers publish
+//! `AgentEvent`s on a per-user broadcast channel and `/api/events/{user_id}`
+//! streams them to the frontend as server-sent events, so it can show the
+//! loop working instead of polling.
+
+use actix_web::web::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+/// Events buffered per subscriber before slow clients start missing some
+pub const EVENT_BUFFER: usize = 256;
+
+/// Interval between keep-alive comments on an idle stream
+pub const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+// ============================================================
+// EVENT TYPES
+// ============================================================
+
+/// What the agent is doing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentEventKind {
+    SensingStarted,
+    SensingComplete,
+    PlanningStarted,
+    PlanStepGenerated,
+    PlanComplete,
+    ReflectionStarted,
+    ReflectionComplete,
+    Failed,
+}
+
+impl AgentEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentEventKind::SensingStarted => "sensing_started",
+            AgentEventKind::SensingComplete => "sensing_complete",
+            AgentEventKind::PlanningStarted => "planning_started",
+            AgentEventKind::PlanStepGenerated => "plan_step_generated",
+            AgentEventKind::PlanComplete => "plan_complete",
+            AgentEventKind::ReflectionStarted => "reflection_started",
+            AgentEventKind::ReflectionComplete => "reflection_complete",
+            AgentEventKind::Failed => "failed",
+        }
+    }
+
+    /// Loop phase this event belongs to ("sense", "plan", "learn")
+    pub fn phase(&self) -> &'static str {
+        match self {
+            AgentEventKind::SensingStarted | AgentEventKind::SensingComplete => "sense",
+            AgentEventKind::PlanningStarted
+            | AgentEventKind::PlanStepGenerated
+            | AgentEventKind::PlanComplete => "plan",
+            AgentEventKind::ReflectionStarted | AgentEventKind::ReflectionComplete => "learn",
+            AgentEventKind::Failed => "error",
+        }
+    }
+}
+
+/// A single live activity event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentEvent {
+    pub id: String,
+    pub user_id: String,
+    pub kind: AgentEventKind,
+    pub phase: String,
+    pub message: String,
+    /// Optional payload (e.g. the generated step)
+    pub data: Option<serde_json::Value>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AgentEvent {
+    pub fn new(user_id: &str, kind: AgentEventKind, message: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            kind,
+            phase: kind.phase().to_string(),
+            message: message.to_string(),
+            data: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Encode as an SSE frame (`id`, `event` and a JSON `data` line)
+    pub fn to_sse(&self) -> Bytes {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        Bytes::from(format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.id,
+            self.kind.as_str(),
+            json
+        ))
+    }
+}
+
+// ============================================================
+// EVENT BUS
+// ============================================================
+
+/// Fan-out of agent events, with one channel per user so a busy user
+/// cannot make anyone else's stream lag
+pub struct EventBus {
+    senders: Mutex<HashMap<String, broadcast::Sender<AgentEvent>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Publish an event to its user's streams; a no-op when nobody is listening
+    pub fn publish(&self, event: AgentEvent) {
+        let mut senders = self.senders.lock().unwrap();
+        let user_id = event.user_id.clone();
+        if let Some(sender) = senders.get(&user_id) {
+            if sender.send(event).is_err() {
+                senders.remove(&user_id);
+            }
+        }
+    }
+
+    /// Receive one user's events
+    pub fn subscribe(&self, user_id: &str) -> broadcast::Receiver<AgentEvent> {
+        let mut senders = self.senders.lock().unwrap();
+        // Drop channels whose streams have all disconnected
+        senders.retain(|_, sender| sender.receiver_count() > 0);
+        senders
+            .entry(user_id.to_string())
+            .or_insert_with(|| broadcast::channel(EVENT_BUFFER).0)
+            .subscribe()
+    }
+
+    /// SSE byte stream of one user's events, with periodic keep-alive comments
+    pub fn stream_for_user(
+        &self,
+        user_id: &str,
+    ) -> impl Stream<Item = Result<Bytes, actix_web::Error>> {
+        let receiver = self.subscribe(user_id);
+
+        // Tell the client to wait 3s before reconnecting
+        let opening = stream::once(async { Ok(Bytes::from_static(b"retry: 3000\n\n")) });
+        let events = stream::unfold(receiver, |mut receiver| async move {
+            match tokio::time::timeout(KEEP_ALIVE, receiver.recv()).await {
+                Ok(Ok(event)) => Some((Ok(event.to_sse()), receiver)),
+                Ok(Err(RecvError::Lagged(missed))) => {
+                    let comment = format!(": missed {} event(s)\n\n", missed);
+                    Some((Ok(Bytes::from(comment)), receiver))
+                }
+                Ok(Err(RecvError::Closed)) => None,
+                Err(_) => Some((Ok(Bytes::from_static(b": keep-alive\n\n")), receiver)),
+            }
+        });
+
+        opening.chain(events)
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_sse_frame() {
+        let event = AgentEvent::new("u1", AgentEventKind::PlanStepGenerated, "Step 1: Learn SQL")
+            .with_data(serde_json::json!({ "order": 1 }));
+        let frame = String::from_utf8(event.to_sse().to_vec()).unwrap();
+
+        assert!(frame.starts_with(&format!("id: {}\n", event.id)));
+        assert!(frame.contains("event: plan_step_generated\n"));
+        assert!(frame.contains("\"phase\":\"plan\""));
+        assert!(frame.ends_with("\n\n"));
+    }
+
+    #[actix_rt::test]
+    async fn test_stream_filters_by_user() {
+        let bus = EventBus::new();
+        let stream = bus.stream_for_user("u1");
+        futures_util::pin_mut!(stream);
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), Bytes::from_static(b"retry: 3000\n\n"));
+
+        bus.publish(AgentEvent::new("u2", AgentEventKind::SensingStarted, "other user"));
+        bus.publish(AgentEvent::new("u1", AgentEventKind::ReflectionComplete, "done"));
+
+        let frame = stream.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(frame.contains("event: reflection_complete"));
+        assert!(!frame.contains("other user"));
+    }
+
+    #[actix_rt::test]
+    async fn test_busy_user_does_not_lag_others() {
+        let bus = EventBus::new();
+        let busy = bus.subscribe("busy");
+        let mut quiet = bus.subscribe("quiet");
+
+        for i in 0..EVENT_BUFFER * 2 {
+            bus.publish(AgentEvent::new("busy", AgentEventKind::PlanStepGenerated, &format!("Step {}", i)));
+        }
+        bus.publish(AgentEvent::new("quiet", AgentEventKind::PlanComplete, "done"));
+
+        let event = quiet.recv().await.unwrap();
+        assert_eq!(event.kind, AgentEventKind::PlanComplete);
+
+        // Channels without listeners are dropped
+        drop(busy);
+        bus.publish(AgentEvent::new("busy", AgentEventKind::PlanComplete, "done"));
+        assert!(!bus.senders.lock().unwrap().contains_key("busy"));
+    }
+}
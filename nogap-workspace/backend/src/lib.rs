@@ -3,11 +3,17 @@
 //! An Agentic AI Career Development Assistant with:
 //! - Sense → Plan → Learn loop
 //! - Agent Memory Timeline
-//! - Weekly Reflection
+//! - Live agent activity over server-sent events
+//! - Weekly Reflection (scheduled, with optional email digest)
 //! - Human-in-the-loop editing
+//! - Roadmap calendar feed (iCalendar)
 
 pub mod agent;
 pub mod api;
+pub mod auth;
+pub mod calendar;
+pub mod events;
+pub mod scheduler;
 
 pub use agent::*;
 pub use api::*;
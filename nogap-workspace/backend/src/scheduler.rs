@@ -0,0 +1,508 @@
+//! Reflection Scheduler Module
+//!
+//! Runs the LEARN phase on a timer so users get a weekly reflection without
+//! asking for one. Each scheduled reflection is stored in the memory timeline
+//! and, for users who opted in, emailed as a short digest.
+
+use crate::agent::memory::{MemoryEvent, MemoryEventType, MemoryQuery, MemoryStore};
+use crate::agent::reflection::{ReflectionConfig, ReflectionGenerator, WeeklyReflection};
+use crate::api::AppState;
+use crate::events::{AgentEvent, AgentEventKind};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+// ============================================================
+// CONFIGURATION
+// ============================================================
+
+/// Scheduler settings
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    pub enabled: bool,
+    /// Minimum time between two reflections for the same user
+    pub interval_hours: i64,
+    /// How often the scheduler looks for users that are due
+    pub check_interval_secs: u64,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_hours: 24 * 7,
+            check_interval_secs: 60 * 60,
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// Longest accepted reflection interval (one year)
+    pub const MAX_INTERVAL_HOURS: i64 = 24 * 365;
+
+    /// Read `CAREER_AGENT_REFLECTION_SCHEDULER` (set to 0/false to disable),
+    /// `CAREER_AGENT_REFLECTION_INTERVAL_HOURS` (1 to `MAX_INTERVAL_HOURS`) and
+    /// `CAREER_AGENT_REFLECTION_CHECK_SECS` (at least 1)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("CAREER_AGENT_REFLECTION_SCHEDULER")
+                .map(|v| !matches!(v.as_str(), "0" | "false" | "no" | "off"))
+                .unwrap_or(defaults.enabled),
+            interval_hours: std::env::var("CAREER_AGENT_REFLECTION_INTERVAL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|hours| (1..=Self::MAX_INTERVAL_HOURS).contains(hours))
+                .unwrap_or(defaults.interval_hours),
+            check_interval_secs: std::env::var("CAREER_AGENT_REFLECTION_CHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|secs| secs.max(1))
+                .unwrap_or(defaults.check_interval_secs),
+        }
+    }
+}
+
+/// Email digest settings
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    /// Path to a sendmail-compatible binary; digests are disabled when unset
+    pub sendmail_path: Option<String>,
+    pub from_address: String,
+}
+
+impl DigestConfig {
+    /// Read `CAREER_AGENT_SENDMAIL` and `CAREER_AGENT_DIGEST_FROM`
+    pub fn from_env() -> Self {
+        Self {
+            sendmail_path: std::env::var("CAREER_AGENT_SENDMAIL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            from_address: std::env::var("CAREER_AGENT_DIGEST_FROM")
+                .unwrap_or_else(|_| "career-agent@localhost".to_string()),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DigestError {
+    #[error("Invalid email address: {0}")]
+    InvalidEmail(String),
+
+    #[error("Failed to send digest: {0}")]
+    Mail(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+}
+
+// ============================================================
+// DIGEST SUBSCRIPTIONS
+// ============================================================
+
+/// A user's email digest preference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestSubscription {
+    pub user_id: String,
+    pub email: String,
+    pub enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// SQLite-backed digest subscriptions (shares the memory database)
+pub struct DigestStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DigestStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> rusqlite::Result<Self> {
+        conn.lock().unwrap().execute(
+            "CREATE TABLE IF NOT EXISTS digest_subscriptions (
+                user_id TEXT PRIMARY KEY,
+                email TEXT NOT NULL,
+                enabled INTEGER NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Create or replace a user's subscription
+    pub fn subscribe(
+        &self,
+        user_id: &str,
+        email: &str,
+        enabled: bool,
+    ) -> Result<DigestSubscription, DigestError> {
+        let email = email.trim();
+        if !is_valid_email(email) {
+            return Err(DigestError::InvalidEmail(email.to_string()));
+        }
+
+        let subscription = DigestSubscription {
+            user_id: user_id.to_string(),
+            email: email.to_string(),
+            enabled,
+            updated_at: Utc::now(),
+        };
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO digest_subscriptions (user_id, email, enabled, updated_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                subscription.user_id,
+                subscription.email,
+                subscription.enabled,
+                subscription.updated_at.to_rfc3339()
+            ],
+        )?;
+        Ok(subscription)
+    }
+
+    pub fn get(&self, user_id: &str) -> Result<Option<DigestSubscription>, DigestError> {
+        let row: Option<(String, bool, String)> = self
+            .conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT email, enabled, updated_at FROM digest_subscriptions WHERE user_id = ?1",
+                [user_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        Ok(row.map(|(email, enabled, updated_at)| DigestSubscription {
+            user_id: user_id.to_string(),
+            email,
+            enabled,
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }))
+    }
+}
+
+/// Basic shape check; also rejects anything that could inject mail headers
+fn is_valid_email(email: &str) -> bool {
+    let mut parts = email.splitn(2, '@');
+    let (local, domain) = match (parts.next(), parts.next()) {
+        (Some(l), Some(d)) => (l, d),
+        _ => return false,
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.contains(|c: char| c.is_whitespace() || c.is_control() || c == ',' || c == '<' || c == '>')
+}
+
+// ============================================================
+// MAILER
+// ============================================================
+
+/// Delivers digest emails
+pub trait Mailer: Send + Sync {
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), DigestError>;
+}
+
+/// Sends mail by piping it to `sendmail -t -oi`
+pub struct SendmailMailer {
+    sendmail_path: String,
+    from_address: String,
+}
+
+impl SendmailMailer {
+    pub fn new(sendmail_path: &str, from_address: &str) -> Self {
+        Self {
+            sendmail_path: sendmail_path.to_string(),
+            from_address: from_address.to_string(),
+        }
+    }
+}
+
+impl Mailer for SendmailMailer {
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), DigestError> {
+        let mut child = Command::new(&self.sendmail_path)
+            .args(["-t", "-oi"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| DigestError::Mail(format!("{}: {}", self.sendmail_path, e)))?;
+
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+            self.from_address, to, subject, body
+        );
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(message.as_bytes())
+                .map_err(|e| DigestError::Mail(e.to_string()))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| DigestError::Mail(e.to_string()))?;
+        if !output.status.success() {
+            return Err(DigestError::Mail(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Build the configured mailer, or `None` when digests are disabled
+pub fn build_mailer(config: &DigestConfig) -> Option<Arc<dyn Mailer>> {
+    config
+        .sendmail_path
+        .as_ref()
+        .map(|path| Arc::new(SendmailMailer::new(path, &config.from_address)) as Arc<dyn Mailer>)
+}
+
+/// Subject and plain-text body for a reflection digest
+pub fn format_digest(reflection: &WeeklyReflection) -> (String, String) {
+    let subject = format!(
+        "Your weekly career reflection: {:.0}% through your roadmap",
+        reflection.metrics.progress_percentage
+    );
+
+    let mut body = format!(
+        "Week of {} to {}\n\n{}\n",
+        reflection.week_start.format("%b %d"),
+        reflection.week_end.format("%b %d, %Y"),
+        reflection.summary
+    );
+    let sections = [
+        ("What changed", &reflection.changes),
+        ("Why the plan adapted", &reflection.adaptations),
+        ("Suggested next steps", &reflection.suggestions),
+    ];
+    for (heading, items) in sections {
+        if items.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("\n{}:\n", heading));
+        for item in items {
+            body.push_str(&format!("  - {}\n", item));
+        }
+    }
+    body.push_str(&format!(
+        "\nSteps completed this week: {}\n",
+        reflection.metrics.steps_completed
+    ));
+
+    (subject, body)
+}
+
+// ============================================================
+// SCHEDULER
+// ============================================================
+
+/// Generate reflections for every user with a roadmap whose last reflection
+/// (or roadmap, if they have none yet) is older than the configured interval.
+/// Returns the reflections that were generated.
+pub fn run_due_reflections(
+    state: &AppState,
+    config: &SchedulerConfig,
+    now: DateTime<Utc>,
+) -> Vec<WeeklyReflection> {
+    let roadmaps: Vec<_> = state.roadmaps.lock().unwrap().values().cloned().collect();
+    let interval = Duration::hours(
+        config
+            .interval_hours
+            .clamp(1, SchedulerConfig::MAX_INTERVAL_HOURS),
+    );
+
+    let mut generator = ReflectionGenerator::new(ReflectionConfig::default());
+    if let Some(provider) = &state.llm {
+        generator = generator.with_llm(provider.clone());
+    }
+
+    let mut generated = Vec::new();
+    for roadmap in roadmaps {
+        let user_id = roadmap.user_id.clone();
+        let last = last_reflection_at(&state.memory_store, &user_id).unwrap_or(roadmap.generated_at);
+        if now - last < interval {
+            continue;
+        }
+
+        let memory = match state.memory_store.get_user_memory(&user_id) {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Skipping scheduled reflection for {}: {}", user_id, e);
+                continue;
+            }
+        };
+        state.events.publish(AgentEvent::new(
+            &user_id,
+            AgentEventKind::ReflectionStarted,
+            "Generating scheduled weekly reflection",
+        ));
+        let reflection = generator.generate_weekly_reflection(&memory, &roadmap);
+
+        // Store and record, keeping the full reflection on the timeline event
+        state.reflection_store.save_reflection(reflection.clone());
+        let event = MemoryEvent::new(&user_id, MemoryEventType::ReflectionGenerated, &reflection.summary)
+            .with_metadata(serde_json::to_value(&reflection).unwrap_or_default())
+            .with_tags(vec!["weekly".to_string(), "scheduled".to_string()]);
+        if let Err(e) = state.memory_store.record_event(&event) {
+            log::warn!("Failed to record scheduled reflection for {}: {}", user_id, e);
+        }
+
+        if let Err(e) = send_digest(state, &reflection) {
+            log::warn!("Digest for {} not sent: {}", user_id, e);
+        }
+        state.events.publish(
+            AgentEvent::new(&user_id, AgentEventKind::ReflectionComplete, &reflection.summary)
+                .with_data(json!({ "reflection_id": reflection.id, "scheduled": true })),
+        );
+
+        generated.push(reflection);
+    }
+
+    generated
+}
+
+/// Email the reflection if the user subscribed and a mailer is configured
+fn send_digest(state: &AppState, reflection: &WeeklyReflection) -> Result<(), DigestError> {
+    let mailer = match &state.mailer {
+        Some(m) => m,
+        None => return Ok(()),
+    };
+    let subscription = match state.digest_store.get(&reflection.user_id)? {
+        Some(s) if s.enabled => s,
+        _ => return Ok(()),
+    };
+
+    let (subject, body) = format_digest(reflection);
+    mailer.send(&subscription.email, &subject, &body)
+}
+
+fn last_reflection_at(store: &MemoryStore, user_id: &str) -> Option<DateTime<Utc>> {
+    let query = MemoryQuery {
+        event_type: Some(MemoryEventType::ReflectionGenerated),
+        limit: Some(1),
+        ..Default::default()
+    };
+    store
+        .query_events(user_id, &query)
+        .ok()
+        .and_then(|page| page.events.first().map(|e| e.timestamp))
+}
+
+/// Start the background loop on the current actix runtime
+pub fn spawn(state: Arc<AppState>, config: SchedulerConfig) {
+    if !config.enabled {
+        return;
+    }
+
+    actix_web::rt::spawn(async move {
+        let mut ticker =
+            actix_web::rt::time::interval(std::time::Duration::from_secs(config.check_interval_secs.max(1)));
+        loop {
+            ticker.tick().await;
+            let state = state.clone();
+            let config = config.clone();
+            // Reflection generation may call an LLM, so keep it off the async workers
+            match actix_web::web::block(move || run_due_reflections(&state, &config, Utc::now())).await {
+                Ok(reflections) if !reflections.is_empty() => {
+                    log::info!("Generated {} scheduled reflection(s)", reflections.len());
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Reflection scheduler run failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::planner::{CareerPlanner, PlannerConfig, PlannerInput};
+    use crate::agent::types::{CareerGoal, CareerRule, ResumeData};
+    use std::collections::HashSet;
+
+    struct RecordingMailer {
+        sent: Mutex<Vec<(String, String)>>,
+    }
+
+    impl Mailer for RecordingMailer {
+        fn send(&self, to: &str, subject: &str, _body: &str) -> Result<(), DigestError> {
+            self.sent.lock().unwrap().push((to.to_string(), subject.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_run_due_reflections_records_and_emails() {
+        let mailer = Arc::new(RecordingMailer {
+            sent: Mutex::new(Vec::new()),
+        });
+        let mut state = AppState::in_memory().unwrap();
+        state.mailer = Some(mailer.clone());
+
+        let mut roadmap = CareerPlanner::new(PlannerConfig::default()).generate_roadmap(&PlannerInput {
+            resume: ResumeData {
+                user_id: "test_user".to_string(),
+                ..Default::default()
+            },
+            assessments: Vec::new(),
+            goal: CareerGoal::new("test_user", "Test Goal"),
+            available_rules: vec![CareerRule::new("test", "Test Step", "technical_skill")],
+            deferred_steps: HashSet::new(),
+        });
+        roadmap.generated_at = Utc::now() - Duration::days(8);
+        state.roadmaps.lock().unwrap().insert("test_user".to_string(), roadmap);
+        state.digest_store.subscribe("test_user", "test@example.com", true).unwrap();
+
+        let config = SchedulerConfig::default();
+        let generated = run_due_reflections(&state, &config, Utc::now());
+        assert_eq!(generated.len(), 1);
+
+        let page = state
+            .memory_store
+            .query_events(
+                "test_user",
+                &MemoryQuery {
+                    tag: Some("scheduled".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert!(page.events[0].metadata.is_some());
+        assert_eq!(mailer.sent.lock().unwrap()[0].0, "test@example.com");
+
+        // Not due again until the interval has passed
+        assert!(run_due_reflections(&state, &config, Utc::now()).is_empty());
+        assert_eq!(
+            run_due_reflections(&state, &config, Utc::now() + Duration::days(8)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_digest_subscription_validation() {
+        let conn = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        let store = DigestStore::new(conn).unwrap();
+
+        assert!(matches!(
+            store.subscribe("u1", "not-an-email", true),
+            Err(DigestError::InvalidEmail(_))
+        ));
+        assert!(store
+            .subscribe("u1", "a@example.com\r\nBcc: x@evil.com", true)
+            .is_err());
+
+        store.subscribe("u1", "a@example.com", true).unwrap();
+        store.subscribe("u1", "a@example.com", false).unwrap();
+        assert!(!store.get("u1").unwrap().unwrap().enabled);
+        assert!(store.get("u2").unwrap().is_none());
+    }
+}
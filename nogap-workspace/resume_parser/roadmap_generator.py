@@ -775,6 +775,7 @@ def generate_roadmap(
     session: dict,
     enhanced_snapshot: dict = None,
     profile_signals: dict = None,
+    job_gap_goals: List[dict] = None,
 ) -> dict:
     """
     Generate a roadmap for the current strategy.
@@ -790,6 +791,7 @@ def generate_roadmap(
         session: AgentSession dict with current_strategy
         enhanced_snapshot: Optional enhanced career snapshot
         profile_signals: Optional profile signals
+        job_gap_goals: Optional learning goals from the latest job gap analysis
         
     Returns:
         dict with either:
@@ -844,6 +846,9 @@ def generate_roadmap(
                 "reason": f"Strategy '{strategy_name}' is in EXECUTE state but no roadmap generator exists",
             }
         
+        # Learning goals from a job description gap analysis become skill actions
+        roadmap.actions.extend(job_gap_actions(roadmap.roadmap_id, job_gap_goals or []))
+        
         # Step 4: Return successful roadmap
         return {
             "roadmap": roadmap.to_dict(),
@@ -863,6 +868,28 @@ def generate_roadmap(
         }
 
 
+def job_gap_actions(roadmap_id: str, goals: List[dict]) -> List[RoadmapAction]:
+    """
+    Turn job gap goals (serialized CareerRules) into skill actions.
+    
+    Goals are scheduled one after another using their week estimates.
+    """
+    actions = []
+    deadline_days = 0
+    for index, goal in enumerate(goals, start=1):
+        deadline_days += (goal.get("estimated_weeks") or 4) * 7
+        priority = goal.get("priority", "medium")
+        actions.append(RoadmapAction(
+            action_id=f"{roadmap_id}-gap-{index}",
+            title=goal.get("title", "Close skill gap"),
+            description=goal.get("description", ""),
+            deadline_days=deadline_days,
+            priority=priority if priority in ("critical", "high", "medium") else "medium",
+            category="skill",
+        ))
+    return actions
+
+
 # =============================================================================
 # ROADMAP INVALIDATION
 # =============================================================================
@@ -931,11 +958,19 @@ def main():
             print(json.dumps({"error": "No input received on stdin"}))
             sys.exit(1)
         
-        session_data = json.loads(stdin_data)
+        input_data = json.loads(stdin_data)
         print(f"[roadmap_generator.py] Parsed session successfully", file=sys.stderr)
         
-        # Generate roadmap
-        result = generate_roadmap(session_data)
+        # The backend wraps the session with its optional extras
+        if "session" in input_data:
+            result = generate_roadmap(
+                input_data["session"],
+                enhanced_snapshot=input_data.get("enhanced_snapshot") or None,
+                profile_signals=input_data.get("profile_signals") or None,
+                job_gap_goals=input_data.get("job_gap_goals"),
+            )
+        else:
+            result = generate_roadmap(input_data)
         
         # Log eligibility check
         if result.get("eligible"):
@@ -208,6 +208,26 @@ def test_all_strategy_types_in_execute():
     print("\n[PASS] All strategy types successfully generated roadmaps")
 
 
+def test_job_gap_goals_become_actions():
+    """Test that job gap goals are added to the roadmap as skill actions."""
+    print("\n[TEST] Job gap goals -> skill actions")
+    
+    session = create_test_session("execute", "SkillGapPatch")
+    goals = [
+        {"title": "Learn Docker", "description": "Missing from resume", "priority": "critical", "estimated_weeks": 2},
+        {"title": "Learn AWS", "description": "Missing from resume", "priority": "medium", "estimated_weeks": None},
+    ]
+    result = generate_roadmap(session, job_gap_goals=goals)
+    
+    actions = result["roadmap"]["actions"]
+    gap_actions = [a for a in actions if "-gap-" in a["action_id"]]
+    assert [a["title"] for a in gap_actions] == ["Learn Docker", "Learn AWS"]
+    assert [a["deadline_days"] for a in gap_actions] == [14, 42]
+    assert all(a["category"] == "skill" for a in gap_actions)
+    
+    print("    -> [PASS] Gap goals scheduled after each other")
+
+
 def run_all_tests():
     """Run all roadmap gating tests."""
     print("=" * 60)
@@ -224,6 +244,7 @@ def run_all_tests():
         test_full_roadmap_generation_execute,
         test_full_roadmap_generation_explore_blocked,
         test_all_strategy_types_in_execute,
+        test_job_gap_goals_become_actions,
     ]
     
     passed = 0